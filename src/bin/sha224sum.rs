@@ -0,0 +1,344 @@
+//! A `sha224sum`-like command-line utility.
+//!
+//! This binary is optional and can be enabled using the `cli` Cargo feature.
+
+use std::ffi::{OsStr, OsString};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use chksum_sha2_224 as sha2_224;
+use sha2_224::checkfile::{self, Entry, Mode, Status};
+
+const NAME: &str = "sha224sum";
+
+const USAGE: &str = "\
+Usage: sha224sum [OPTION]... [FILE]...
+Print or check SHA224 (224-bit) checksums.
+
+With no FILE, or when FILE is -, read standard input.
+
+  -b, --binary          read in binary mode
+  -c, --check           read checksums from the FILEs and check them
+      --tag             create a BSD-style checksum
+  -t, --text            read in text mode (default)
+  -z, --zero            end each output line with NUL, not newline,
+                          and disable file name escaping
+  -r, --recursive       compute digests of directories from their contents,
+                          directories listed in checked FILEs are always
+                          verified this way
+
+The following five options are useful only when verifying checksums:
+      --ignore-missing  don't fail or report status for missing files
+      --quiet           don't print OK for each successfully verified file
+      --status          don't output anything, status code shows success
+      --strict          exit non-zero for improperly formatted checksum lines
+  -w, --warn            warn about improperly formatted checksum lines
+
+  -h, --help            display this help and exit
+      --version         output version information and exit
+";
+
+#[derive(Debug, Default)]
+struct Options {
+    check: bool,
+    mode: Option<Mode>,
+    zero: bool,
+    recursive: bool,
+    ignore_missing: bool,
+    quiet: bool,
+    status: bool,
+    strict: bool,
+    warn: bool,
+    files: Vec<OsString>,
+}
+
+enum Command {
+    Run(Options),
+    Help,
+    Version,
+}
+
+fn parse(args: impl IntoIterator<Item = OsString>) -> Result<Command, String> {
+    let mut options = Options::default();
+    let mut only_files = false;
+    for arg in args {
+        if only_files || arg == "-" || !arg.to_string_lossy().starts_with('-') {
+            options.files.push(arg);
+            continue;
+        }
+        let arg = arg.to_string_lossy();
+        let flags: Vec<String> = if let Some(long) = arg.strip_prefix("--") {
+            if long.is_empty() {
+                only_files = true;
+                continue;
+            }
+            vec![long.to_owned()]
+        } else {
+            arg.chars().skip(1).map(String::from).collect()
+        };
+        for flag in flags {
+            match flag.as_str() {
+                "b" | "binary" => set_mode(&mut options, Mode::Binary)?,
+                "t" | "text" => set_mode(&mut options, Mode::Text)?,
+                "tag" => set_mode(&mut options, Mode::Tag)?,
+                "c" | "check" => options.check = true,
+                "z" | "zero" => options.zero = true,
+                "r" | "recursive" => options.recursive = true,
+                "ignore-missing" => options.ignore_missing = true,
+                "quiet" => options.quiet = true,
+                "status" => options.status = true,
+                "strict" => options.strict = true,
+                "w" | "warn" => options.warn = true,
+                "h" | "help" => return Ok(Command::Help),
+                "version" => return Ok(Command::Version),
+                flag if flag.chars().count() == 1 => return Err(format!("invalid option -- '{flag}'")),
+                flag => return Err(format!("unrecognized option '--{flag}'")),
+            }
+        }
+    }
+
+    if options.check {
+        if options.mode == Some(Mode::Tag) {
+            return Err(String::from("the --tag option is meaningless when verifying checksums"));
+        }
+        if options.mode.is_some() {
+            return Err(String::from(
+                "the --binary and --text options are meaningless when verifying checksums",
+            ));
+        }
+    } else {
+        let check_only = [
+            (options.ignore_missing, "--ignore-missing"),
+            (options.quiet, "--quiet"),
+            (options.status, "--status"),
+            (options.strict, "--strict"),
+            (options.warn, "--warn"),
+        ];
+        if let Some((_, option)) = check_only.into_iter().find(|(enabled, _)| *enabled) {
+            return Err(format!(
+                "the {option} option is meaningful only when verifying checksums"
+            ));
+        }
+    }
+    if options.files.is_empty() {
+        options.files.push(OsString::from("-"));
+    }
+    Ok(Command::Run(options))
+}
+
+fn set_mode(options: &mut Options, mode: Mode) -> Result<(), String> {
+    match (options.mode, mode) {
+        (Some(Mode::Tag), Mode::Text) | (Some(Mode::Text), Mode::Tag) => {
+            Err(String::from("--tag does not support --text mode"))
+        },
+        // the tag format doesn't distinguish modes, so binary mode doesn't override it
+        (Some(Mode::Tag), Mode::Binary) => Ok(()),
+        _ => {
+            options.mode = Some(mode);
+            Ok(())
+        },
+    }
+}
+
+fn is_stdin(file: &OsStr) -> bool {
+    file == "-"
+}
+
+fn digest(file: &OsStr, recursive: bool) -> sha2_224::Result<sha2_224::Digest> {
+    if is_stdin(file) {
+        return sha2_224::chksum(io::stdin());
+    }
+    let path = Path::new(file);
+    if !recursive && path.is_dir() {
+        let error = io::Error::other("Is a directory");
+        return Err(error.into());
+    }
+    sha2_224::chksum(path)
+}
+
+fn run_hash(options: &Options) -> io::Result<bool> {
+    let mut stdout = io::stdout().lock();
+    let mode = options.mode.unwrap_or_default();
+    let terminator = if options.zero { b'\0' } else { b'\n' };
+    let mut ok = true;
+    for file in &options.files {
+        match digest(file, options.recursive) {
+            Ok(digest) => {
+                let mut line = Entry::new(digest, file, mode).to_bytes(!options.zero);
+                line.push(terminator);
+                stdout.write_all(&line)?;
+            },
+            Err(error) => {
+                stdout.flush()?;
+                eprintln!("{NAME}: {}: {error}", Path::new(file).display());
+                ok = false;
+            },
+        }
+    }
+    stdout.flush()?;
+    Ok(ok)
+}
+
+fn open_checkfile(file: &OsStr) -> io::Result<Box<dyn BufRead>> {
+    if is_stdin(file) {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(file)?)))
+    }
+}
+
+/// Verifies the entry, directories are verified by the digests of their contents like `--recursive` computes them.
+fn verify(entry: &Entry) -> Status {
+    if !entry.path.is_dir() {
+        return entry.check();
+    }
+    match sha2_224::chksum(&entry.path) {
+        Ok(digest) if digest == entry.digest => Status::Ok,
+        Ok(_) => Status::Failed,
+        Err(error) => Status::Missing(error),
+    }
+}
+
+fn plural(count: usize, singular: &str, plural: &str) -> String {
+    if count == 1 {
+        format!("{count} {singular}")
+    } else {
+        format!("{count} {plural}")
+    }
+}
+
+fn run_check(options: &Options) -> io::Result<bool> {
+    let mut stdout = io::stdout().lock();
+    let mut ok = true;
+    'files: for file in &options.files {
+        let name = if is_stdin(file) {
+            String::from("standard input")
+        } else {
+            Path::new(file).display().to_string()
+        };
+        let reader = match open_checkfile(file) {
+            Ok(reader) => reader,
+            Err(error) => {
+                eprintln!("{NAME}: {name}: {error}");
+                ok = false;
+                continue;
+            },
+        };
+
+        let (mut checked, mut malformed) = (0, 0);
+        let (mut verified, mut failed, mut missing) = (0, 0, 0);
+        // every line is reported as soon as it is verified
+        for line in checkfile::lines(reader) {
+            let (number, entry) = match line {
+                Ok(line) => line,
+                Err(error) => {
+                    stdout.flush()?;
+                    eprintln!("{NAME}: {name}: {error}");
+                    ok = false;
+                    continue 'files;
+                },
+            };
+            let Ok(entry) = entry else {
+                malformed += 1;
+                if options.warn {
+                    stdout.flush()?;
+                    eprintln!("{NAME}: {name}: {number}: improperly formatted SHA224 checksum line");
+                }
+                continue;
+            };
+            checked += 1;
+            // paths are written as raw bytes, so names which are not valid UTF-8 are kept intact
+            let path = entry.path.as_os_str().as_encoded_bytes();
+            match verify(&entry) {
+                Status::Ok => {
+                    verified += 1;
+                    if !options.quiet && !options.status {
+                        stdout.write_all(&[path, b": OK\n"].concat())?;
+                    }
+                },
+                Status::Failed => {
+                    verified += 1;
+                    failed += 1;
+                    if !options.status {
+                        stdout.write_all(&[path, b": FAILED\n"].concat())?;
+                    }
+                },
+                Status::Missing(sha2_224::Error::Io(error))
+                    if options.ignore_missing && error.kind() == ErrorKind::NotFound => {},
+                Status::Missing(error) => {
+                    missing += 1;
+                    if !options.status {
+                        stdout.flush()?;
+                        eprintln!("{NAME}: {}: {error}", entry.path.display());
+                        stdout.write_all(&[path, b": FAILED open or read\n"].concat())?;
+                    }
+                },
+            }
+        }
+        stdout.flush()?;
+
+        if checked == 0 {
+            eprintln!("{NAME}: {name}: no properly formatted checksum lines found");
+            ok = false;
+            continue;
+        }
+        if !options.status {
+            if malformed > 0 {
+                let lines = plural(malformed, "line is", "lines are");
+                eprintln!("{NAME}: WARNING: {lines} improperly formatted");
+            }
+            if missing > 0 {
+                let files = plural(missing, "listed file", "listed files");
+                eprintln!("{NAME}: WARNING: {files} could not be read");
+            }
+            if failed > 0 {
+                let checksums = plural(failed, "computed checksum", "computed checksums");
+                eprintln!("{NAME}: WARNING: {checksums} did NOT match");
+            }
+        }
+        if options.ignore_missing && verified == 0 && missing == 0 {
+            if !options.status {
+                eprintln!("{NAME}: {name}: no file was verified");
+            }
+            ok = false;
+        }
+        let strict_failed = options.strict && malformed > 0;
+        ok &= failed == 0 && missing == 0 && !strict_failed;
+    }
+    Ok(ok)
+}
+
+fn main() -> ExitCode {
+    let options = match parse(std::env::args_os().skip(1)) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::Help) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        },
+        Ok(Command::Version) => {
+            println!("{NAME} ({}) {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        },
+        Err(message) => {
+            eprintln!("{NAME}: {message}");
+            eprintln!("Try '{NAME} --help' for more information.");
+            return ExitCode::FAILURE;
+        },
+    };
+    let result = if options.check {
+        run_check(&options)
+    } else {
+        run_hash(&options)
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) if error.kind() == ErrorKind::BrokenPipe => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("{NAME}: {error}");
+            ExitCode::FAILURE
+        },
+    }
+}
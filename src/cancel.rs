@@ -0,0 +1,77 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::Error;
+
+/// A token which cancels hashing operations.
+///
+/// Clones of the token share the state, so a clone can be cancelled from another thread. Cancelled operations fail
+/// with [`Error::Io`] which wraps [`Cancelled`].
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha2_224 as sha2_224;
+/// use sha2_224::dir::DirHasher;
+/// use sha2_224::{CancellationToken, Cancelled};
+///
+/// # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+/// let token = CancellationToken::new();
+/// let hasher = DirHasher::new(path).cancellation(token.clone());
+/// token.cancel();
+///
+/// let error = hasher.chksum().unwrap_err();
+/// assert!(Cancelled::matches(&error));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new token which is not cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels all operations which use the token or its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks whether the token is cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns the error of a cancelled operation when the token is cancelled.
+    pub(crate) fn check(&self) -> io::Result<()> {
+        if self.is_cancelled() {
+            Err(io::Error::other(Cancelled))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// An error of a cancelled operation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("Operation cancelled")]
+pub struct Cancelled;
+
+impl Cancelled {
+    /// Checks whether the error comes from a cancelled operation.
+    #[must_use]
+    pub fn matches(error: &Error) -> bool {
+        match error {
+            Error::Io(error) => error.get_ref().is_some_and(|error| error.is::<Self>()),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,517 @@
+//! This module provides generation and verification of checksum files compatible with the `sha224sum` utility.
+//!
+//! Every line of a checksum file describes a single path. Three formats of lines are supported:
+//!
+//! * text mode - `<hex>  <path>`,
+//! * binary mode - `<hex> *<path>`,
+//! * BSD tag - `SHA224 (<path>) = <hex>`.
+//!
+//! Paths which contain a backslash, a line feed or a carriage return are escaped, and such lines start with an
+//! additional backslash, exactly like GNU coreutils do. Lines starting with `#` and empty lines are ignored while
+//! checking.
+//!
+//! Paths are written and read as raw bytes, so names which are not valid UTF-8 round-trip on Unix. On other platforms,
+//! such names can only be written, and lines with paths which are not valid UTF-8 are improperly formatted.
+//!
+//! Relative paths are resolved against the current working directory.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use chksum_sha2_224 as sha2_224;
+//! use sha2_224::checkfile::{self, Mode};
+//!
+//! # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+//! let mut manifest = Vec::new();
+//! checkfile::write(&mut manifest, [path], Mode::Text)?;
+//!
+//! let report = checkfile::check(manifest.as_slice())?;
+//! assert!(report.is_ok());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::{hash, Digest, FormatError, Result};
+
+/// The algorithm name used by BSD-style tag lines.
+pub const TAG: &str = "SHA224";
+
+/// A format of a checksum line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// The `<hex>  <path>` format.
+    #[default]
+    Text,
+    /// The `<hex> *<path>` format.
+    Binary,
+    /// The BSD-style `SHA224 (<path>) = <hex>` format.
+    Tag,
+}
+
+/// A single line of a checksum file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The expected digest.
+    pub digest: Digest,
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The format of the line.
+    pub mode: Mode,
+}
+
+impl Entry {
+    /// Creates a new entry.
+    #[must_use]
+    pub fn new(digest: Digest, path: impl Into<PathBuf>, mode: Mode) -> Self {
+        let path = path.into();
+        Self { digest, path, mode }
+    }
+
+    /// Returns the line without the line terminator.
+    ///
+    /// When `escape` is `true`, paths containing special characters are escaped, which is required for
+    /// newline-terminated checksum files. Paths which are not valid UTF-8 are converted lossily, use
+    /// [`Entry::to_bytes`] to write them unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    /// use sha2_224::checkfile::{Entry, Mode};
+    ///
+    /// let entry = Entry::new(sha2_224::hash(b""), "file.txt", Mode::Tag);
+    /// assert_eq!(
+    ///     entry.to_line(true),
+    ///     "SHA224 (file.txt) = d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_line(&self, escape: bool) -> String {
+        String::from_utf8_lossy(&self.to_bytes(escape)).into_owned()
+    }
+
+    /// Returns the line without the line terminator as bytes, with the path in the native encoding of the platform.
+    ///
+    /// When `escape` is `true`, paths containing special characters are escaped, which is required for
+    /// newline-terminated checksum files.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    /// use sha2_224::checkfile::{Entry, Mode};
+    ///
+    /// let entry = Entry::new(sha2_224::hash(b""), "file\ntxt", Mode::Text);
+    /// assert_eq!(
+    ///     entry.to_bytes(true),
+    ///     b"\\d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f  file\\ntxt"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self, escape: bool) -> Vec<u8> {
+        let path = self.path.as_os_str().as_encoded_bytes();
+        let escaped = escape && path.iter().any(|byte| matches!(byte, b'\\' | b'\n' | b'\r'));
+        let digest = self.digest.to_hex_lowercase();
+
+        let mut line = Vec::with_capacity(1 + TAG.len() + 4 + path.len() * 2 + digest.len());
+        if escaped {
+            line.push(b'\\');
+        }
+        match self.mode {
+            Mode::Text | Mode::Binary => {
+                line.extend_from_slice(digest.as_bytes());
+                line.extend_from_slice(if self.mode == Mode::Text { b"  " } else { b" *" });
+                extend_path(&mut line, path, escaped);
+            },
+            Mode::Tag => {
+                line.extend_from_slice(TAG.as_bytes());
+                line.extend_from_slice(b" (");
+                extend_path(&mut line, path, escaped);
+                line.extend_from_slice(b") = ");
+                line.extend_from_slice(digest.as_bytes());
+            },
+        }
+        line
+    }
+
+    /// Computes the digest of the file and compares it with the expected one.
+    ///
+    /// Directories are not read, like GNU coreutils do, other entries are read like regular files.
+    #[must_use]
+    pub fn check(&self) -> Status {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(error) => return Status::Missing(error.into()),
+        };
+        if file.metadata().is_ok_and(|metadata| metadata.is_dir()) {
+            let error = io::Error::other("Is a directory");
+            return Status::Missing(error.into());
+        }
+        match crate::chksum(file) {
+            Ok(digest) if digest == self.digest => Status::Ok,
+            Ok(_) => Status::Failed,
+            Err(error) => Status::Missing(error),
+        }
+    }
+}
+
+/// Appends the path to the line, escaping special characters when needed.
+fn extend_path(line: &mut Vec<u8>, path: &[u8], escaped: bool) {
+    if !escaped {
+        line.extend_from_slice(path);
+        return;
+    }
+    for &byte in path {
+        match byte {
+            b'\\' => line.extend_from_slice(b"\\\\"),
+            b'\n' => line.extend_from_slice(b"\\n"),
+            b'\r' => line.extend_from_slice(b"\\r"),
+            byte => line.push(byte),
+        }
+    }
+}
+
+impl Display for Entry {
+    /// Formats the escaped line, paths which are not valid UTF-8 are converted lossily.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_line(true))
+    }
+}
+
+impl FromStr for Entry {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> std::result::Result<Self, Self::Err> {
+        parse_line(line)
+    }
+}
+
+/// Parses a single line of a checksum file.
+///
+/// A trailing line feed, optionally preceded by a carriage return, and leading whitespace are ignored.
+///
+/// # Errors
+///
+/// Returns [`ParseError`] when the line is improperly formatted.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+/// use sha2_224::checkfile::{self, Mode};
+///
+/// let entry = checkfile::parse_line(
+///     "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f *file.txt\n",
+/// )
+/// .unwrap();
+/// assert_eq!(entry.digest, sha2_224::hash(b""));
+/// assert_eq!(entry.path.to_str(), Some("file.txt"));
+/// assert_eq!(entry.mode, Mode::Binary);
+/// ```
+pub fn parse_line(line: &str) -> std::result::Result<Entry, ParseError> {
+    parse_bytes(line.as_bytes())
+}
+
+/// Parses a single line of a checksum file given as bytes, with the path in the native encoding of the platform.
+///
+/// It works like [`parse_line`], but accepts paths which are not valid UTF-8 on Unix.
+///
+/// # Errors
+///
+/// Returns [`ParseError`] when the line is improperly formatted.
+pub fn parse_bytes(line: &[u8]) -> std::result::Result<Entry, ParseError> {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    let start = line
+        .iter()
+        .position(|byte| !matches!(byte, b' ' | b'\t'))
+        .unwrap_or(line.len());
+    let line = &line[start..];
+    let (escaped, line) = match line.strip_prefix(b"\\") {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+
+    let tag = split_once(line, b" (").filter(|(algorithm, _)| {
+        !algorithm.is_empty()
+            && algorithm
+                .iter()
+                .all(|&byte| byte.is_ascii_alphanumeric() || byte == b'-')
+    });
+    let (digest, path, mode) = if let Some((algorithm, rest)) = tag {
+        if algorithm != TAG.as_bytes() {
+            // the algorithm consists of ASCII characters only
+            let algorithm = String::from_utf8_lossy(algorithm).into_owned();
+            return Err(ParseError::UnsupportedAlgorithm { algorithm });
+        }
+        let (path, digest) = rsplit_once(rest, b") = ").ok_or(ParseError::InvalidFormat)?;
+        (digest, path, Mode::Tag)
+    } else {
+        let digest = line.get(..hash::digest::LENGTH_HEX).ok_or(ParseError::InvalidFormat)?;
+        let rest = &line[digest.len()..];
+        let (mode, path) = if let Some(path) = rest.strip_prefix(b"  ") {
+            (Mode::Text, path)
+        } else if let Some(path) = rest.strip_prefix(b" *") {
+            (Mode::Binary, path)
+        } else {
+            return Err(ParseError::InvalidFormat);
+        };
+        (digest, path, mode)
+    };
+
+    if path.is_empty() {
+        return Err(ParseError::InvalidFormat);
+    }
+    let digest = std::str::from_utf8(digest).map_err(|_| ParseError::InvalidFormat)?;
+    let digest = Digest::try_from_hex(digest)?;
+    let path = if escaped { unescape(path)? } else { path.to_vec() };
+    Ok(Entry::new(digest, path_from_bytes(path)?, mode))
+}
+
+/// Splits the bytes at the first occurrence of the separator.
+fn split_once<'a>(bytes: &'a [u8], separator: &[u8]) -> Option<(&'a [u8], &'a [u8])> {
+    let index = bytes.windows(separator.len()).position(|window| window == separator)?;
+    Some((&bytes[..index], &bytes[index + separator.len()..]))
+}
+
+/// Splits the bytes at the last occurrence of the separator.
+fn rsplit_once<'a>(bytes: &'a [u8], separator: &[u8]) -> Option<(&'a [u8], &'a [u8])> {
+    let index = bytes.windows(separator.len()).rposition(|window| window == separator)?;
+    Some((&bytes[..index], &bytes[index + separator.len()..]))
+}
+
+fn unescape(path: &[u8]) -> std::result::Result<Vec<u8>, ParseError> {
+    let mut unescaped = Vec::with_capacity(path.len());
+    let mut bytes = path.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte != b'\\' {
+            unescaped.push(byte);
+            continue;
+        }
+        let byte = match bytes.next() {
+            Some(b'\\') => b'\\',
+            Some(b'n') => b'\n',
+            Some(b'r') => b'\r',
+            _ => return Err(ParseError::InvalidEscape),
+        };
+        unescaped.push(byte);
+    }
+    Ok(unescaped)
+}
+
+/// Converts raw bytes of a path into the path.
+#[cfg(unix)]
+fn path_from_bytes(path: Vec<u8>) -> std::result::Result<PathBuf, ParseError> {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    Ok(PathBuf::from(OsString::from_vec(path)))
+}
+
+/// Converts raw bytes of a path into the path, only UTF-8 is supported.
+#[cfg(not(unix))]
+fn path_from_bytes(path: Vec<u8>) -> std::result::Result<PathBuf, ParseError> {
+    String::from_utf8(path)
+        .map(PathBuf::from)
+        .map_err(|_| ParseError::InvalidFormat)
+}
+
+/// Computes digests of the given paths and writes them as newline-terminated checksum lines.
+///
+/// Directories are rejected, like by [`Entry::check`], so every written line can be verified later.
+///
+/// # Errors
+///
+/// Returns [`Error`](crate::Error) when a path is a directory, when a digest cannot be computed or when the writer
+/// fails.
+pub fn write<W, P>(mut writer: W, paths: impl IntoIterator<Item = P>, mode: Mode) -> Result<()>
+where
+    W: Write,
+    P: AsRef<Path>,
+{
+    for path in paths {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        if file.metadata()?.is_dir() {
+            let error = io::Error::other("Is a directory");
+            return Err(error.into());
+        }
+        let digest = crate::chksum(file)?;
+        let mut line = Entry::new(digest, path, mode).to_bytes(true);
+        line.push(b'\n');
+        writer.write_all(&line)?;
+    }
+    Ok(())
+}
+
+/// Reads checksum lines and verifies every properly formatted one.
+///
+/// Use [`lines`] to verify and report entries one by one instead of collecting them.
+///
+/// # Errors
+///
+/// Returns [`Error`](crate::Error) when the reader fails. Failures of the checked paths are reported in [`Report`].
+pub fn check(reader: impl BufRead) -> Result<Report> {
+    let mut report = Report::default();
+    for line in lines(reader) {
+        let (line, entry) = line?;
+        match entry {
+            Ok(entry) => {
+                let status = entry.check();
+                report.checked.push(Checked {
+                    line,
+                    path: entry.path,
+                    status,
+                });
+            },
+            Err(error) => report.malformed.push(Malformed { line, error }),
+        }
+    }
+    Ok(report)
+}
+
+/// Returns an iterator over checksum lines which parses them one by one without verifying any path.
+///
+/// Every item consists of the line number, starting from 1, and the parsed entry. Comments and empty lines are
+/// skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224::checkfile;
+///
+/// let manifest =
+///     "# comment\nd14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f  empty.txt\n";
+/// for line in checkfile::lines(manifest.as_bytes()) {
+///     let (number, entry) = line.unwrap();
+///     assert_eq!(number, 2);
+///     assert_eq!(entry.unwrap().path.to_str(), Some("empty.txt"));
+/// }
+/// ```
+pub fn lines<R: BufRead>(reader: R) -> Lines<R> {
+    Lines {
+        reader,
+        buffer: Vec::new(),
+        number: 0,
+    }
+}
+
+/// An iterator over checksum lines, created by [`lines`].
+#[derive(Debug)]
+pub struct Lines<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    number: usize,
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = Result<(usize, std::result::Result<Entry, ParseError>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buffer.clear();
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => {},
+                Err(error) => return Some(Err(error.into())),
+            }
+            self.number += 1;
+            if self.buffer.starts_with(b"#") || matches!(self.buffer.as_slice(), b"\n" | b"\r\n") {
+                continue;
+            }
+            return Some(Ok((self.number, parse_bytes(&self.buffer))));
+        }
+    }
+}
+
+/// A result of the verification of a single path.
+#[derive(Debug)]
+pub enum Status {
+    /// The digest matches the expected one.
+    Ok,
+    /// The digest doesn't match the expected one.
+    Failed,
+    /// The path cannot be opened or read, or it is a directory.
+    Missing(crate::Error),
+}
+
+/// A verified line of a checksum file.
+#[derive(Debug)]
+pub struct Checked {
+    /// The line number, starting from 1.
+    pub line: usize,
+    /// The verified path.
+    pub path: PathBuf,
+    /// The result of the verification.
+    pub status: Status,
+}
+
+/// An improperly formatted line of a checksum file.
+#[derive(Debug)]
+pub struct Malformed {
+    /// The line number, starting from 1.
+    pub line: usize,
+    /// The reason why the line cannot be parsed.
+    pub error: ParseError,
+}
+
+/// A summary of the checksum file verification.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// Properly formatted lines in the order of appearance.
+    pub checked: Vec<Checked>,
+    /// Improperly formatted lines in the order of appearance.
+    pub malformed: Vec<Malformed>,
+}
+
+impl Report {
+    /// Returns the number of paths with a mismatched digest.
+    #[must_use]
+    pub fn failed(&self) -> usize {
+        self.checked
+            .iter()
+            .filter(|checked| matches!(checked.status, Status::Failed))
+            .count()
+    }
+
+    /// Returns the number of paths which cannot be opened or read.
+    #[must_use]
+    pub fn missing(&self) -> usize {
+        self.checked
+            .iter()
+            .filter(|checked| matches!(checked.status, Status::Missing(_)))
+            .count()
+    }
+
+    /// Returns `true` when at least one path has been verified and all verified paths match their digests.
+    ///
+    /// Improperly formatted lines are not taken into account.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        !self.checked.is_empty() && self.failed() == 0 && self.missing() == 0
+    }
+}
+
+/// An error type for the checksum line parsing.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ParseError {
+    /// Represents a line which doesn't match any of the supported formats.
+    #[error("Improperly formatted checksum line")]
+    InvalidFormat,
+    /// Represents a BSD-style tag line with an algorithm other than SHA-2 224.
+    #[error("Unsupported algorithm `{algorithm}`")]
+    UnsupportedAlgorithm { algorithm: String },
+    /// Represents an invalid escape sequence in an escaped path.
+    #[error("Invalid escape sequence")]
+    InvalidEscape,
+    /// Represents an invalid digest.
+    #[error(transparent)]
+    InvalidDigest(#[from] FormatError),
+}
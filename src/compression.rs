@@ -0,0 +1,82 @@
+//! This module contains the SHA-2 224 compression function used by resumable [`SHA2_224`](crate::SHA2_224) hashes.
+//!
+//! The [`chksum_hash_sha2_224`] crate keeps the chaining values of its `Update` and `State` structures private, so an
+//! in-progress computation can neither be exported from it nor restored into it. This module reimplements only the
+//! compression function of FIPS PUB 180-4 over explicit chaining values, which is what [`State`](crate::state::State)
+//! needs. Other hashes keep using [`hash::Update`](crate::hash::Update). The constants are copied verbatim from the
+//! upstream crate. The results are checked against the NIST vectors and against the upstream crate by the integration
+//! tests in `tests/state.rs`, through resumable hashes.
+
+use crate::hash;
+
+const BLOCK_LENGTH_BYTES: usize = hash::BLOCK_LENGTH_BYTES;
+
+/// The initial chaining values.
+#[allow(clippy::unreadable_literal)]
+#[rustfmt::skip]
+pub(crate) const H: [u32; 8] = [
+    0xC1059ED8, 0x367CD507, 0x3070DD17, 0xF70E5939,
+    0xFFC00B31, 0x68581511, 0x64F98FA7, 0xBEFA4FA4,
+];
+
+/// The round constants.
+#[allow(clippy::unreadable_literal)]
+#[rustfmt::skip]
+const K: [u32; 64] = [
+    0x428A2F98, 0x71374491, 0xB5C0FBCF, 0xE9B5DBA5,
+    0x3956C25B, 0x59F111F1, 0x923F82A4, 0xAB1C5ED5,
+    0xD807AA98, 0x12835B01, 0x243185BE, 0x550C7DC3,
+    0x72BE5D74, 0x80DEB1FE, 0x9BDC06A7, 0xC19BF174,
+    0xE49B69C1, 0xEFBE4786, 0x0FC19DC6, 0x240CA1CC,
+    0x2DE92C6F, 0x4A7484AA, 0x5CB0A9DC, 0x76F988DA,
+    0x983E5152, 0xA831C66D, 0xB00327C8, 0xBF597FC7,
+    0xC6E00BF3, 0xD5A79147, 0x06CA6351, 0x14292967,
+    0x27B70A85, 0x2E1B2138, 0x4D2C6DFC, 0x53380D13,
+    0x650A7354, 0x766A0ABB, 0x81C2C92E, 0x92722C85,
+    0xA2BFE8A1, 0xA81A664B, 0xC24B8B70, 0xC76C51A3,
+    0xD192E819, 0xD6990624, 0xF40E3585, 0x106AA070,
+    0x19A4C116, 0x1E376C08, 0x2748774C, 0x34B0BCB5,
+    0x391C0CB3, 0x4ED8AA4A, 0x5B9CCA4F, 0x682E6FF3,
+    0x748F82EE, 0x78A5636F, 0x84C87814, 0x8CC70208,
+    0x90BEFFFA, 0xA4506CEB, 0xBEF9A3F7, 0xC67178F2,
+];
+
+/// Processes a single block with the SHA-2 compression function.
+pub(crate) fn compress(chaining: [u32; 8], block: &[u8; BLOCK_LENGTH_BYTES]) -> [u32; 8] {
+    let mut w = [0u32; 64];
+    for (w, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_be_bytes(bytes.try_into().expect("chunk must have exact length"));
+    }
+    for index in 16..64 {
+        let s0 = w[index - 15].rotate_right(7) ^ w[index - 15].rotate_right(18) ^ (w[index - 15] >> 3);
+        let s1 = w[index - 2].rotate_right(17) ^ w[index - 2].rotate_right(19) ^ (w[index - 2] >> 10);
+        w[index] = w[index - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[index - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = chaining;
+    for (w, k) in w.into_iter().zip(K) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(k).wrapping_add(w);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    let mut chaining = chaining;
+    for (value, update) in chaining.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *value = value.wrapping_add(update);
+    }
+    chaining
+}
@@ -0,0 +1,1181 @@
+//! This module provides parallel and configurable hashing of directories.
+//!
+//! By default, the digest of a directory is computed exactly like [`chksum`](crate::chksum) does it: entries of every
+//! directory are sorted by their paths, symbolic links are followed, and contents of all files are hashed as a single
+//! stream. Because of that, the hashing itself is sequential, but files are opened and read ahead by a pool of worker
+//! threads, which hides the latency of the file system when there are many files.
+//!
+//! [`DirHasher`] allows to exclude entries with gitignore-style patterns, to skip hidden entries, to limit the depth
+//! of the traversal and to choose how symbolic links are treated. It also provides an opt-in metadata mode, in which
+//! the digest covers relative paths, types and permissions of entries, so renaming a file or changing its mode is
+//! detected too.
+//!
+//! Besides [`DirHasher::chksum`], the digest can be computed with [`DirHasher::async_chksum`], which honors the same
+//! options, including progress reporting and cancellation, except the number of threads and memory mapping.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use chksum_sha2_224 as sha2_224;
+//! use sha2_224::dir::{DirHasher, SymlinkPolicy};
+//!
+//! # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+//! let digest = DirHasher::new(path).threads(8).chksum()?;
+//! assert_eq!(digest, sha2_224::chksum(path)?);
+//!
+//! let digest = DirHasher::new(path)
+//!     .ignore(".git/")
+//!     .ignore("*.log")
+//!     .symlinks(SymlinkPolicy::Skip)
+//!     .include_hidden(false)
+//!     .max_depth(8)
+//!     .chksum()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Ignore patterns
+//!
+//! Patterns follow the rules of `.gitignore` files and are matched against paths relative to the hashed directory:
+//!
+//! * `*` matches any sequence of characters except `/`, `?` matches any single character except `/`, and `[...]`
+//!   matches a single character from the set, which can be negated with `!` or `^`,
+//! * `**` as a whole path segment matches any number of segments, except a trailing one which matches at least one,
+//!   so `dir/**` excludes everything inside `dir`, but not `dir` itself,
+//! * a pattern without a `/` other than a trailing one matches an entry at any depth, other patterns are anchored to
+//!   the hashed directory,
+//! * a pattern with a trailing `/` matches only directories,
+//! * a pattern starting with `!` includes again entries excluded by previous patterns, the last matching pattern wins,
+//! * a backslash escapes the following character.
+//!
+//! Contents of an excluded directory are never visited, so they cannot be included again.
+//!
+//! # Metadata mode
+//!
+//! In the metadata mode, enabled with [`DirHasher::metadata`], the digest is computed over a canonical encoding, which
+//! is stable and can be reproduced by other tools. It starts with a header:
+//!
+//! | Length | Description                                                            |
+//! |--------|------------------------------------------------------------------------|
+//! | 9      | the `SHA224DIR` signature in ASCII                                     |
+//! | 1      | the version of the encoding, equal to `1`                              |
+//! | 1      | flags, `0x01` when modification times are included, `0x02` for owners |
+//!
+//! The header is followed by one record per hashed entry, including directories and the hashed path itself, in the
+//! same order as in the default mode, so a directory precedes its entries. All integers are big-endian.
+//!
+//! | Length | Description                                                                                     |
+//! |--------|-------------------------------------------------------------------------------------------------|
+//! | 1      | the type of the entry, `d` for a directory, `f` for a file and `l` for a symbolic link in ASCII |
+//! | 8      | the length of the path                                                                          |
+//! | n      | the path relative to the hashed path, with `/` separators, empty for the hashed path itself     |
+//! | 4      | permission bits, the lowest 12 bits of the Unix mode                                            |
+//! | 8 + 4  | only with modification times, signed seconds and nanoseconds since the Unix epoch               |
+//! | 4 + 4  | only with owners, the user and the group identifiers                                            |
+//! | 28     | only for a file, the SHA-2 224 digest of its contents                                           |
+//! | 8 + n  | only for a symbolic link, the length of its target path and the target path itself              |
+//!
+//! Paths are encoded in UTF-8, or in the native encoding of the platform when they aren't valid Unicode. Symbolic
+//! links are recorded only with [`SymlinkPolicy::HashTarget`], followed links are recorded like their targets. On
+//! platforms other than Unix, permission bits are `0o444` for read-only files and `0o644` otherwise, with `0o111` added
+//! for directories, and owners are zeros.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Formatter};
+use std::fs::{self, File, Metadata};
+use std::io::{self, ErrorKind, IsTerminal, Read};
+use std::num::NonZeroUsize;
+#[cfg(feature = "mmap")]
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::UNIX_EPOCH;
+
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::AsyncReadExt;
+
+use crate::options::ProgressFn;
+use crate::{CancellationToken, Digest, Error, Progress, Result, SHA2_224};
+
+/// The number of bytes read from a file at once.
+const CHUNK_LENGTH: usize = 64 * 1024;
+
+/// The number of chunks buffered for every file which is read ahead.
+const CHUNKS_AHEAD: usize = 4;
+
+/// The signature of the metadata mode encoding.
+const SIGNATURE: &[u8; 9] = b"SHA224DIR";
+
+/// The version of the metadata mode encoding.
+const VERSION: u8 = 1;
+
+/// The flag of the metadata mode encoding which marks included modification times.
+const FLAG_MTIMES: u8 = 0x01;
+
+/// The flag of the metadata mode encoding which marks included owners.
+const FLAG_OWNERSHIP: u8 = 0x02;
+
+/// Computes the digest of the directory with the default options.
+///
+/// # Errors
+///
+/// Returns [`Error`] when an entry cannot be read.
+pub fn chksum(path: impl AsRef<Path>) -> Result<Digest> {
+    DirHasher::new(path.as_ref()).chksum()
+}
+
+/// A treatment of symbolic links found inside of the hashed directory.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SymlinkPolicy {
+    /// Symbolic links are followed and their targets are hashed like regular entries.
+    #[default]
+    Follow,
+    /// The target path of a symbolic link is hashed instead of the contents of the target.
+    HashTarget,
+    /// Symbolic links are skipped.
+    Skip,
+    /// Symbolic links cause an error.
+    Error,
+}
+
+/// A callback which receives progress reports.
+#[derive(Clone)]
+struct Callback<'a>(Arc<Mutex<ProgressFn<'a>>>);
+
+impl Debug for Callback<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Callback { .. }")
+    }
+}
+
+/// A configurable directory hasher.
+#[derive(Clone, Debug)]
+pub struct DirHasher<'a> {
+    path: PathBuf,
+    threads: usize,
+    patterns: Vec<Pattern>,
+    symlinks: SymlinkPolicy,
+    include_hidden: bool,
+    max_depth: Option<usize>,
+    metadata: bool,
+    mtimes: bool,
+    ownership: bool,
+    progress: Option<Callback<'a>>,
+    cancellation: Option<CancellationToken>,
+    #[cfg(feature = "mmap")]
+    mmap_threshold: Option<u64>,
+}
+
+impl<'a> DirHasher<'a> {
+    /// Creates a new hasher of the directory or the file with the default options, which produce the same digest as
+    /// [`chksum`](crate::chksum), and one thread per available CPU.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        Self {
+            path: path.into(),
+            threads,
+            patterns: Vec::new(),
+            symlinks: SymlinkPolicy::Follow,
+            include_hidden: true,
+            max_depth: None,
+            metadata: false,
+            mtimes: false,
+            ownership: false,
+            progress: None,
+            cancellation: None,
+            #[cfg(feature = "mmap")]
+            mmap_threshold: None,
+        }
+    }
+
+    /// Sets the number of threads which read files.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the number is zero.
+    #[must_use]
+    pub fn threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "number of threads must be greater than zero");
+        self.threads = threads;
+        self
+    }
+
+    /// Adds a gitignore-style pattern of excluded entries.
+    ///
+    /// Empty patterns and patterns starting with `#` are ignored. Check [`dir`](self) module for the syntax.
+    #[must_use]
+    pub fn ignore(mut self, pattern: impl AsRef<str>) -> Self {
+        if let Some(pattern) = Pattern::new(pattern.as_ref()) {
+            self.patterns.push(pattern);
+        }
+        self
+    }
+
+    /// Sets the treatment of symbolic links.
+    ///
+    /// The hashed path itself is always followed.
+    #[must_use]
+    pub fn symlinks(mut self, symlinks: SymlinkPolicy) -> Self {
+        self.symlinks = symlinks;
+        self
+    }
+
+    /// Sets whether symbolic links are followed.
+    ///
+    /// It is a shorthand for [`SymlinkPolicy::Follow`] and [`SymlinkPolicy::HashTarget`] respectively, so links which
+    /// are not followed still affect the digest.
+    #[must_use]
+    pub fn follow_symlinks(self, follow: bool) -> Self {
+        let symlinks = if follow {
+            SymlinkPolicy::Follow
+        } else {
+            SymlinkPolicy::HashTarget
+        };
+        self.symlinks(symlinks)
+    }
+
+    /// Sets whether entries with names starting with `.` are hashed.
+    #[must_use]
+    pub fn include_hidden(mut self, include_hidden: bool) -> Self {
+        self.include_hidden = include_hidden;
+        self
+    }
+
+    /// Sets the maximum depth of hashed entries.
+    ///
+    /// The hashed path has the depth of zero, its direct entries have the depth of one, and so on.
+    #[must_use]
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets whether the metadata mode is used.
+    ///
+    /// In the metadata mode, the digest covers relative paths, types and permission bits of entries. Check
+    /// [`dir`](self) module for the encoding.
+    #[must_use]
+    pub fn metadata(mut self, metadata: bool) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Sets whether modification times are covered by the digest.
+    ///
+    /// It takes effect only in the metadata mode.
+    #[must_use]
+    pub fn mtimes(mut self, mtimes: bool) -> Self {
+        self.mtimes = mtimes;
+        self
+    }
+
+    /// Sets whether user and group identifiers of owners are covered by the digest.
+    ///
+    /// It takes effect only in the metadata mode.
+    #[must_use]
+    pub fn ownership(mut self, ownership: bool) -> Self {
+        self.ownership = ownership;
+        self
+    }
+
+    /// Sets the callback which is called before every file and after every chunk of its contents is hashed.
+    ///
+    /// The callback runs on the thread which called [`DirHasher::chksum`] or [`DirHasher::manifest`], or in the task
+    /// which awaits [`DirHasher::async_chksum`], so it can borrow local state like the one passed to
+    /// [`ChksumOptions::progress`](crate::ChksumOptions::progress). The total is the sum of lengths of all hashed files
+    /// and symbolic link targets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// use chksum_sha2_224 as sha2_224;
+    /// use sha2_224::dir::DirHasher;
+    ///
+    /// # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+    /// let digest = DirHasher::new(path)
+    ///     .progress(|progress| {
+    ///         if let Some(path) = progress.path() {
+    ///             println!("{}: {} bytes", path.display(), progress.processed());
+    ///         }
+    ///     })
+    ///     .chksum()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn progress(mut self, callback: impl FnMut(&Progress<'_>) + Send + 'a) -> Self {
+        self.progress = Some(Callback(Arc::new(Mutex::new(callback))));
+        self
+    }
+
+    /// Sets the token which cancels hashing, it is checked before every entry and every chunk of contents.
+    #[must_use]
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Sets the minimal length of files which are mapped into memory, `None`, the default, disables mapping.
+    ///
+    /// Check the [safety notes](crate::mmap#safety) before enabling it.
+    #[cfg(feature = "mmap")]
+    #[must_use]
+    pub fn mmap_threshold(mut self, threshold: Option<u64>) -> Self {
+        self.mmap_threshold = threshold;
+        self
+    }
+
+    /// Computes the digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when an entry cannot be read, when a symbolic link is found with [`SymlinkPolicy::Error`] or
+    /// when hashing is cancelled with [`Cancelled`](crate::Cancelled). When several entries fail, the error of the
+    /// first one in the hashing order is returned, exactly like [`chksum`](crate::chksum) does.
+    pub fn chksum(&self) -> Result<Digest> {
+        self.hash(None)
+    }
+
+    /// Computes the digest along with the manifest of hashed files and symbolic links.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] in the same cases as [`DirHasher::chksum`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// use chksum_sha2_224 as sha2_224;
+    /// use sha2_224::dir::DirHasher;
+    ///
+    /// # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+    /// let manifest = DirHasher::new(path).manifest()?;
+    /// assert_eq!(manifest.digest(), sha2_224::chksum(path)?);
+    /// for entry in manifest.entries() {
+    ///     println!("{}  {}", entry.digest, entry.path.display());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn manifest(&self) -> Result<Manifest> {
+        let mut entries = Vec::new();
+        let digest = self.hash(Some(&mut entries))?;
+        Ok(Manifest { digest, entries })
+    }
+
+    /// Computes the digest asynchronously.
+    ///
+    /// Entries are collected on a blocking thread of the runtime, and files are read one by one with asynchronous
+    /// I/O, so the number of threads and the threshold of memory mapping don't apply. The digest is always equal to
+    /// the one computed by [`DirHasher::chksum`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] in the same cases as [`DirHasher::chksum`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// use chksum_sha2_224 as sha2_224;
+    /// use sha2_224::dir::DirHasher;
+    /// use sha2_224::CancellationToken;
+    ///
+    /// # async fn wrapper(path: &Path) -> sha2_224::Result<()> {
+    /// let token = CancellationToken::new();
+    /// let digest = DirHasher::new(path)
+    ///     .cancellation(token.clone())
+    ///     .async_chksum()
+    ///     .await?;
+    /// assert_eq!(digest, sha2_224::async_chksum(path).await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async-runtime-tokio")]
+    pub async fn async_chksum(&self) -> Result<Digest> {
+        let walker = self.walker();
+        let collected = tokio::task::spawn_blocking(move || {
+            let mut entries = Vec::new();
+            let walked = walker.collect(&mut entries);
+            (entries, walked)
+        })
+        .await;
+        let (entries, walked) = match collected {
+            Ok(collected) => collected,
+            Err(error) => {
+                match error.try_into_panic() {
+                    Ok(payload) => std::panic::resume_unwind(payload),
+                    Err(error) => return Err(io::Error::other(error).into()),
+                }
+            },
+        };
+
+        let mut hashing = Hashing::new(self, &entries, None);
+        let mut chunk = vec![0u8; CHUNK_LENGTH];
+        for entry in entries {
+            let Some(mut contents) = hashing.entry(entry.record, entry.listed)? else {
+                continue;
+            };
+            let path = entry.file.expect("file must be hashed from its path");
+            let mut file = tokio::fs::File::open(path).await?;
+            loop {
+                match file.read(&mut chunk).await {
+                    Ok(0) => break,
+                    Ok(length) => hashing.update(&mut contents, &chunk[..length])?,
+                    Err(error) if error.kind() == ErrorKind::Interrupted => {},
+                    Err(error) => return Err(error.into()),
+                }
+            }
+            hashing.finish(contents);
+        }
+        walked?;
+        Ok(hashing.digest())
+    }
+
+    /// Returns a copy of the hasher without the progress callback, which can be moved to another thread.
+    #[cfg(feature = "async-runtime-tokio")]
+    fn walker(&self) -> DirHasher<'static> {
+        DirHasher {
+            path: self.path.clone(),
+            threads: self.threads,
+            patterns: self.patterns.clone(),
+            symlinks: self.symlinks,
+            include_hidden: self.include_hidden,
+            max_depth: self.max_depth,
+            metadata: self.metadata,
+            mtimes: self.mtimes,
+            ownership: self.ownership,
+            progress: None,
+            cancellation: self.cancellation.clone(),
+            #[cfg(feature = "mmap")]
+            mmap_threshold: self.mmap_threshold,
+        }
+    }
+
+    fn hash(&self, manifest: Option<&mut Vec<ManifestEntry>>) -> Result<Digest> {
+        let mut entries = Vec::new();
+        let walked = self.collect(&mut entries);
+        let mut hashing = Hashing::new(self, &entries, manifest);
+        self.hash_entries(entries, &mut hashing)?;
+        walked?;
+        // workers stop taking files when cancelled, so the last files may be incomplete
+        self.check()?;
+        Ok(hashing.digest())
+    }
+
+    /// Collects entries of the hashed path in the hashing order.
+    ///
+    /// An error ends the walk, it is reported after all entries which precede it are hashed.
+    fn collect(&self, entries: &mut Vec<Entry>) -> Result<()> {
+        let metadata = self.path.metadata()?;
+        let root = Node {
+            path: &self.path,
+            relative: String::new(),
+            depth: 0,
+        };
+        self.walk(&root, &metadata, entries)
+    }
+
+    /// Collects entries in the hashing order.
+    fn walk(&self, node: &Node, metadata: &Metadata, entries: &mut Vec<Entry>) -> Result<()> {
+        if !metadata.is_dir() {
+            // everything treat as a file when it is not a directory
+            let record = if self.metadata {
+                self.record(b'f', node.path, metadata)?
+            } else {
+                Vec::new()
+            };
+            let file = Some(node.path.to_path_buf());
+            let listed = Some(Listed::File(self.relative(node.path)));
+            let size = metadata.len();
+            entries.push(Entry {
+                record,
+                file,
+                listed,
+                size,
+            });
+            return Ok(());
+        }
+        if self.metadata {
+            let record = self.record(b'd', node.path, metadata)?;
+            entries.push(Entry {
+                record,
+                file: None,
+                listed: None,
+                size: 0,
+            });
+        }
+        if self.max_depth.is_some_and(|max_depth| node.depth >= max_depth) {
+            return Ok(());
+        }
+
+        let children: io::Result<Vec<_>> = fs::read_dir(node.path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect();
+        let mut children = children?;
+        children.sort();
+        for path in children {
+            self.check()?;
+            let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+            if !self.include_hidden && name.starts_with('.') {
+                continue;
+            }
+            let relative = if node.relative.is_empty() {
+                name.into_owned()
+            } else {
+                format!("{}/{name}", node.relative)
+            };
+            let child = Node {
+                path: &path,
+                relative,
+                depth: node.depth + 1,
+            };
+
+            let link = path.symlink_metadata()?;
+            let metadata = if link.is_symlink() {
+                match self.symlinks {
+                    SymlinkPolicy::Follow => path.metadata()?,
+                    // links which are not followed are matched against patterns like files
+                    _ if self.is_ignored(&child.relative, false) => continue,
+                    SymlinkPolicy::HashTarget => {
+                        let target = fs::read_link(&path)?;
+                        let target = target.as_os_str().as_encoded_bytes();
+                        let listed = ManifestEntry {
+                            path: self.relative(&path),
+                            digest: crate::hash(target),
+                            size: target.len() as u64,
+                        };
+                        let record = if self.metadata {
+                            let mut record = self.record(b'l', &path, &link)?;
+                            record.extend_from_slice(&(target.len() as u64).to_be_bytes());
+                            record.extend_from_slice(target);
+                            record
+                        } else {
+                            target.to_vec()
+                        };
+                        let size = listed.size;
+                        let listed = Some(Listed::Link(listed));
+                        entries.push(Entry {
+                            record,
+                            file: None,
+                            listed,
+                            size,
+                        });
+                        continue;
+                    },
+                    SymlinkPolicy::Skip => continue,
+                    SymlinkPolicy::Error => {
+                        let error = io::Error::other(format!("Unexpected symbolic link `{}`", path.display()));
+                        return Err(error.into());
+                    },
+                }
+            } else {
+                link
+            };
+            if self.is_ignored(&child.relative, metadata.is_dir()) {
+                continue;
+            }
+            self.walk(&child, &metadata, entries)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the path of the entry with the given relative path.
+    fn join(&self, relative: &Path) -> PathBuf {
+        if relative.as_os_str().is_empty() {
+            self.path.clone()
+        } else {
+            self.path.join(relative)
+        }
+    }
+
+    /// Returns the error of a cancelled operation when the token is cancelled.
+    fn check(&self) -> io::Result<()> {
+        match &self.cancellation {
+            Some(cancellation) => cancellation.check(),
+            None => Ok(()),
+        }
+    }
+
+    /// Calls the callback, if any.
+    fn report(&self, processed: u64, total: u64, path: &Path) {
+        if let Some(Callback(progress)) = &self.progress {
+            let mut progress = progress.lock().expect("progress lock must not be poisoned");
+            progress(&Progress::new(processed, Some(total), Some(path)));
+        }
+    }
+
+    /// Returns the path relative to the hashed path.
+    fn relative(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.path).unwrap_or(path).to_path_buf()
+    }
+
+    /// Encodes the common fields of a metadata mode record.
+    fn record(&self, kind: u8, path: &Path, metadata: &Metadata) -> io::Result<Vec<u8>> {
+        let relative = self.relative(path);
+        let mut encoded = Vec::new();
+        for component in relative.components() {
+            if !encoded.is_empty() {
+                encoded.push(b'/');
+            }
+            encoded.extend_from_slice(component.as_os_str().as_encoded_bytes());
+        }
+
+        let mut record = vec![kind];
+        record.extend_from_slice(&(encoded.len() as u64).to_be_bytes());
+        record.extend_from_slice(&encoded);
+        record.extend_from_slice(&permissions(metadata).to_be_bytes());
+        if self.mtimes {
+            let (seconds, nanoseconds) = mtime(metadata)?;
+            record.extend_from_slice(&seconds.to_be_bytes());
+            record.extend_from_slice(&nanoseconds.to_be_bytes());
+        }
+        if self.ownership {
+            let (user, group) = ownership(metadata);
+            record.extend_from_slice(&user.to_be_bytes());
+            record.extend_from_slice(&group.to_be_bytes());
+        }
+        Ok(record)
+    }
+
+    /// Checks whether the last matching pattern excludes the entry.
+    fn is_ignored(&self, relative: &str, is_dir: bool) -> bool {
+        self.patterns
+            .iter()
+            .rev()
+            .find(|pattern| pattern.matches(relative, is_dir))
+            .is_some_and(|pattern| !pattern.negated)
+    }
+
+    /// Reads the file chunk by chunk, stops at the first error or when the receiver is gone.
+    ///
+    /// Files which are mapped into memory are sent in chunks too, so progress and cancellation work the same way.
+    fn read_file(&self, path: &Path, sender: &mpsc::SyncSender<Result<Chunk>>) {
+        let mut file = match File::open(path) {
+            Ok(file) if file.is_terminal() => {
+                let _ = sender.send(Err(Error::IsTerminal));
+                return;
+            },
+            Ok(file) => file,
+            Err(error) => {
+                let _ = sender.send(Err(error.into()));
+                return;
+            },
+        };
+        #[cfg(feature = "mmap")]
+        if let Some(map) = self
+            .mmap_threshold
+            .and_then(|threshold| crate::mmap::map(&file, threshold))
+        {
+            let map = Arc::new(map);
+            for start in (0..map.len()).step_by(CHUNK_LENGTH) {
+                let end = map.len().min(start + CHUNK_LENGTH);
+                if sender.send(Ok(Chunk::Map(Arc::clone(&map), start..end))).is_err() {
+                    break;
+                }
+            }
+            return;
+        }
+        loop {
+            let mut chunk = vec![0u8; CHUNK_LENGTH];
+            match file.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(length) => {
+                    chunk.truncate(length);
+                    if sender.send(Ok(Chunk::Buffer(chunk))).is_err() {
+                        break;
+                    }
+                },
+                Err(error) if error.kind() == ErrorKind::Interrupted => {},
+                Err(error) => {
+                    let _ = sender.send(Err(error.into()));
+                    break;
+                },
+            }
+        }
+    }
+
+    fn hash_entries(&self, entries: Vec<Entry>, hashing: &mut Hashing<'_, '_>) -> Result<()> {
+        let (records, files): (Vec<_>, Vec<_>) = entries
+            .into_iter()
+            .map(|entry| ((entry.record, entry.listed), entry.file))
+            .unzip();
+        let (senders, receivers): (Vec<_>, Vec<_>) = files.iter().map(|_| mpsc::sync_channel(CHUNKS_AHEAD)).unzip();
+        let jobs = Mutex::new(files.into_iter().zip(senders));
+        let stopped = AtomicBool::new(false);
+        let threads = self.threads.min(receivers.len());
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| {
+                    // jobs are taken in order, so the entry awaited by the hashing thread is always being read
+                    loop {
+                        let job = jobs.lock().expect("jobs lock must not be poisoned").next();
+                        let Some((file, sender)) = job else {
+                            break;
+                        };
+                        if stopped.load(Ordering::Relaxed) || self.check().is_err() {
+                            break;
+                        }
+                        if let Some(path) = file {
+                            self.read_file(&path, &sender);
+                        }
+                    }
+                });
+            }
+
+            let result = records
+                .into_iter()
+                .zip(receivers)
+                .try_for_each(|((record, listed), receiver)| {
+                    if let Some(mut contents) = hashing.entry(record, listed)? {
+                        for chunk in receiver {
+                            hashing.update(&mut contents, chunk?.as_ref())?;
+                        }
+                        hashing.finish(contents);
+                    }
+                    Ok(())
+                });
+            if result.is_err() {
+                stopped.store(true, Ordering::Relaxed);
+            }
+            result
+        })
+    }
+}
+
+/// Hashing of collected entries, shared by the synchronous and the asynchronous hashing.
+struct Hashing<'a, 'b> {
+    hasher: &'a DirHasher<'b>,
+    hash: SHA2_224,
+    manifest: Option<&'a mut Vec<ManifestEntry>>,
+    processed: u64,
+    total: u64,
+}
+
+impl<'a, 'b> Hashing<'a, 'b> {
+    fn new(hasher: &'a DirHasher<'b>, entries: &[Entry], manifest: Option<&'a mut Vec<ManifestEntry>>) -> Self {
+        let mut hash = SHA2_224::new();
+        if hasher.metadata {
+            let mut flags = 0;
+            if hasher.mtimes {
+                flags |= FLAG_MTIMES;
+            }
+            if hasher.ownership {
+                flags |= FLAG_OWNERSHIP;
+            }
+            hash.update(SIGNATURE);
+            hash.update([VERSION, flags]);
+        }
+        let total = entries.iter().map(|entry| entry.size).sum();
+        Self {
+            hasher,
+            hash,
+            manifest,
+            processed: 0,
+            total,
+        }
+    }
+
+    /// Hashes the record of the entry, returns the state of hashing of contents when the entry is a file.
+    fn entry(&mut self, record: Vec<u8>, listed: Option<Listed>) -> Result<Option<Contents>> {
+        self.hasher.check()?;
+        self.hash.update(record);
+        match listed {
+            Some(Listed::File(path)) => {
+                let full_path = self.hasher.progress.is_some().then(|| self.hasher.join(&path));
+                if let Some(full_path) = &full_path {
+                    self.hasher.report(self.processed, self.total, full_path);
+                }
+                // contents are hashed separately only when their digest is needed
+                let hash = (self.hasher.metadata || self.manifest.is_some()).then(SHA2_224::new);
+                let contents = Contents {
+                    path,
+                    full_path,
+                    hash,
+                    size: 0,
+                };
+                Ok(Some(contents))
+            },
+            Some(Listed::Link(entry)) => {
+                self.processed += entry.size;
+                if self.hasher.progress.is_some() {
+                    self.hasher
+                        .report(self.processed, self.total, &self.hasher.join(&entry.path));
+                }
+                if let Some(manifest) = self.manifest.as_deref_mut() {
+                    manifest.push(entry);
+                }
+                Ok(None)
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Hashes a chunk of contents of the file.
+    fn update(&mut self, contents: &mut Contents, chunk: &[u8]) -> Result<()> {
+        self.hasher.check()?;
+        contents.size += chunk.len() as u64;
+        self.processed += chunk.len() as u64;
+        if let Some(full_path) = &contents.full_path {
+            self.hasher.report(self.processed, self.total, full_path);
+        }
+        if let Some(hash) = &mut contents.hash {
+            hash.update(chunk);
+        }
+        if !self.hasher.metadata {
+            self.hash.update(chunk);
+        }
+        Ok(())
+    }
+
+    /// Finishes hashing of contents of the file.
+    fn finish(&mut self, contents: Contents) {
+        let Contents {
+            path,
+            hash: Some(hash),
+            size,
+            ..
+        } = contents
+        else {
+            return;
+        };
+        let digest = hash.digest();
+        // the metadata mode records the digest of contents, so the record has a fixed length
+        if self.hasher.metadata {
+            self.hash.update(digest);
+        }
+        if let Some(manifest) = self.manifest.as_deref_mut() {
+            manifest.push(ManifestEntry { path, digest, size });
+        }
+    }
+
+    fn digest(&self) -> Digest {
+        self.hash.digest()
+    }
+}
+
+/// Hashing of contents of a single file.
+struct Contents {
+    /// The path relative to the hashed path.
+    path: PathBuf,
+    /// The full path, only when progress is reported.
+    full_path: Option<PathBuf>,
+    /// The hash of contents, only when their digest is needed.
+    hash: Option<SHA2_224>,
+    /// The number of hashed bytes.
+    size: u64,
+}
+
+/// A visited path along with its position in the hashed directory.
+struct Node<'a> {
+    path: &'a Path,
+    relative: String,
+    depth: usize,
+}
+
+/// A source of hashed bytes.
+struct Entry {
+    /// Bytes hashed before the file, the metadata mode record or the target path of a symbolic link.
+    record: Vec<u8>,
+    /// The file which contents are hashed.
+    file: Option<PathBuf>,
+    /// The entry of the manifest.
+    listed: Option<Listed>,
+    /// The expected number of hashed bytes of the file or the symbolic link.
+    size: u64,
+}
+
+/// An entry of the manifest before hashing.
+enum Listed {
+    /// A file, which digest and size are known after hashing, with its relative path.
+    File(PathBuf),
+    /// A symbolic link.
+    Link(ManifestEntry),
+}
+
+/// A manifest of a hashed directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Manifest {
+    digest: Digest,
+    entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Returns the digest of the directory.
+    #[must_use]
+    pub fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    /// Returns hashed files and symbolic links in the hashing order.
+    #[must_use]
+    pub fn entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+
+    /// Returns the entry with the given relative path.
+    #[must_use]
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&ManifestEntry> {
+        let path = path.as_ref();
+        self.entries.iter().find(|entry| entry.path == path)
+    }
+}
+
+/// A hashed file or symbolic link.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ManifestEntry {
+    /// The path relative to the hashed path, empty when a single file is hashed.
+    pub path: PathBuf,
+    /// The digest of contents of the file or of the target path of the symbolic link.
+    pub digest: Digest,
+    /// The length of contents of the file or of the target path of the symbolic link.
+    pub size: u64,
+}
+
+/// Differences between two manifests.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diff {
+    /// Entries present only in the new manifest.
+    pub added: Vec<ManifestEntry>,
+    /// Entries present only in the old manifest.
+    pub removed: Vec<ManifestEntry>,
+    /// Pairs of old and new entries with the same path but different contents.
+    pub modified: Vec<(ManifestEntry, ManifestEntry)>,
+}
+
+impl Diff {
+    /// Checks whether manifests have the same entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Compares two manifests, all lists of the result are sorted by paths.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha2_224 as sha2_224;
+/// use sha2_224::dir::{self, DirHasher};
+///
+/// # fn wrapper(old: &Path, new: &Path) -> sha2_224::Result<()> {
+/// let old = DirHasher::new(old).manifest()?;
+/// let new = DirHasher::new(new).manifest()?;
+/// for (_, entry) in dir::diff(&old, &new).modified {
+///     println!("modified {}", entry.path.display());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn diff(old: &Manifest, new: &Manifest) -> Diff {
+    let mut old: BTreeMap<_, _> = old.entries.iter().map(|entry| (&entry.path, entry)).collect();
+    let mut diff = Diff::default();
+    let mut entries: Vec<_> = new.entries.iter().collect();
+    entries.sort_by(|left, right| left.path.cmp(&right.path));
+    for entry in entries {
+        match old.remove(&entry.path) {
+            None => diff.added.push(entry.clone()),
+            Some(previous) if previous != entry => diff.modified.push((previous.clone(), entry.clone())),
+            Some(_) => {},
+        }
+    }
+    diff.removed = old.into_values().cloned().collect();
+    diff
+}
+
+/// Returns permission bits of the entry.
+#[cfg(unix)]
+fn permissions(metadata: &Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+
+    metadata.permissions().mode() & 0o7777
+}
+
+/// Returns permission bits of the entry.
+#[cfg(not(unix))]
+fn permissions(metadata: &Metadata) -> u32 {
+    let mode = if metadata.permissions().readonly() {
+        0o444
+    } else {
+        0o644
+    };
+    if metadata.is_dir() {
+        mode | 0o111
+    } else {
+        mode
+    }
+}
+
+/// Returns identifiers of the user and the group which own the entry.
+#[cfg(unix)]
+fn ownership(metadata: &Metadata) -> (u32, u32) {
+    use std::os::unix::fs::MetadataExt;
+
+    (metadata.uid(), metadata.gid())
+}
+
+/// Returns identifiers of the user and the group which own the entry.
+#[cfg(not(unix))]
+fn ownership(_metadata: &Metadata) -> (u32, u32) {
+    (0, 0)
+}
+
+/// Returns the modification time of the entry as seconds and nanoseconds since the Unix epoch.
+fn mtime(metadata: &Metadata) -> io::Result<(i64, u32)> {
+    let modified = metadata.modified()?;
+    let time = match modified.duration_since(UNIX_EPOCH) {
+        Ok(duration) => {
+            (
+                i64::try_from(duration.as_secs()).unwrap_or(i64::MAX),
+                duration.subsec_nanos(),
+            )
+        },
+        Err(error) => {
+            // times before the epoch have negative seconds and nanoseconds counted forward
+            let duration = error.duration();
+            let seconds = i64::try_from(duration.as_secs()).map_or(i64::MIN, |seconds| -seconds);
+            match duration.subsec_nanos() {
+                0 => (seconds, 0),
+                nanoseconds => (seconds.saturating_sub(1), 1_000_000_000 - nanoseconds),
+            }
+        },
+    };
+    Ok(time)
+}
+
+/// A part of contents of a file.
+enum Chunk {
+    /// Bytes read into a buffer.
+    Buffer(Vec<u8>),
+    /// A range of the file mapped into memory.
+    #[cfg(feature = "mmap")]
+    Map(Arc<memmap2::Mmap>, Range<usize>),
+}
+
+impl AsRef<[u8]> for Chunk {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::Buffer(buffer) => buffer,
+            #[cfg(feature = "mmap")]
+            Self::Map(map, range) => &map[range.clone()],
+        }
+    }
+}
+
+/// A gitignore-style pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pattern {
+    segments: Vec<String>,
+    negated: bool,
+    directory_only: bool,
+}
+
+impl Pattern {
+    fn new(pattern: &str) -> Option<Self> {
+        if pattern.is_empty() || pattern.starts_with('#') {
+            return None;
+        }
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern),
+        };
+        let (directory_only, pattern) = match pattern.strip_suffix('/') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern),
+        };
+        let anchored = pattern.contains('/');
+        let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+        if pattern.is_empty() {
+            return None;
+        }
+        let mut segments: Vec<String> = pattern.split('/').map(ToOwned::to_owned).collect();
+        if !anchored {
+            segments.insert(0, "**".to_owned());
+        }
+        Some(Self {
+            segments,
+            negated,
+            directory_only,
+        })
+    }
+
+    fn matches(&self, relative: &str, is_dir: bool) -> bool {
+        if self.directory_only && !is_dir {
+            return false;
+        }
+        let path: Vec<&str> = relative.split('/').collect();
+        match_segments(&self.segments, &path)
+    }
+}
+
+fn match_segments(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        // a trailing `**` matches everything inside, but not the directory itself
+        Some((first, rest)) if first == "**" && rest.is_empty() => !path.is_empty(),
+        Some((first, rest)) if first == "**" => (0..=path.len()).any(|skipped| match_segments(rest, &path[skipped..])),
+        Some((first, rest)) => {
+            path.split_first().is_some_and(|(segment, path)| {
+                let pattern: Vec<char> = first.chars().collect();
+                let segment: Vec<char> = segment.chars().collect();
+                match_segment(&pattern, &segment) && match_segments(rest, path)
+            })
+        },
+    }
+}
+
+fn match_segment(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skipped| match_segment(rest, &name[skipped..])),
+        Some(('?', rest)) => !name.is_empty() && match_segment(rest, &name[1..]),
+        Some(('[', rest)) => {
+            match match_class(rest, name.first().copied()) {
+                Some((matched, rest)) => matched && match_segment(rest, &name[1..]),
+                // an unclosed bracket is matched literally
+                None => name.first() == Some(&'[') && match_segment(rest, &name[1..]),
+            }
+        },
+        Some(('\\', rest)) if !rest.is_empty() => {
+            name.first() == Some(&rest[0]) && match_segment(&rest[1..], &name[1..])
+        },
+        Some((character, rest)) => name.first() == Some(character) && match_segment(rest, &name[1..]),
+    }
+}
+
+/// Matches the character against the bracket expression and returns the rest of the pattern after the closing
+/// bracket, or `None` when the bracket is not closed.
+fn match_class(pattern: &[char], character: Option<char>) -> Option<(bool, &[char])> {
+    let (negated, mut pattern) = match pattern.first() {
+        Some('!' | '^') => (true, &pattern[1..]),
+        _ => (false, pattern),
+    };
+    let mut matched = false;
+    let mut first = true;
+    loop {
+        let (&start, rest) = pattern.split_first()?;
+        if start == ']' && !first {
+            let matched = character.is_some() && matched != negated;
+            return Some((matched, rest));
+        }
+        first = false;
+        if let (Some('-'), Some(&end)) = (rest.first(), rest.get(1)) {
+            if end != ']' {
+                matched |= character.is_some_and(|character| (start..=end).contains(&character));
+                pattern = &rest[2..];
+                continue;
+            }
+        }
+        matched |= character == Some(start);
+        pattern = rest;
+    }
+}
@@ -0,0 +1,371 @@
+use crate::{hash, Digest, FormatError};
+
+/// The standard base64 encoding with padding.
+pub(crate) const BASE64: Encoding = Encoding {
+    alphabet: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+    padding: true,
+    case_insensitive: false,
+};
+
+/// The standard base64 encoding without padding.
+pub(crate) const BASE64_NOPAD: Encoding = Encoding {
+    padding: false,
+    ..BASE64
+};
+
+/// The URL-safe base64 encoding without padding.
+pub(crate) const BASE64_URL: Encoding = Encoding {
+    alphabet: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+    padding: false,
+    case_insensitive: false,
+};
+
+/// The URL-safe base64 encoding with padding.
+pub(crate) const BASE64_URL_PAD: Encoding = Encoding {
+    padding: true,
+    ..BASE64_URL
+};
+
+/// The base32 encoding with uppercase letters and padding.
+pub(crate) const BASE32: Encoding = Encoding {
+    alphabet: b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+    padding: true,
+    case_insensitive: true,
+};
+
+/// The base32 encoding with uppercase letters and without padding.
+pub(crate) const BASE32_NOPAD: Encoding = Encoding {
+    padding: false,
+    ..BASE32
+};
+
+/// The base32 encoding with lowercase letters and padding.
+pub(crate) const BASE32_LOWER: Encoding = Encoding {
+    alphabet: b"abcdefghijklmnopqrstuvwxyz234567",
+    padding: true,
+    case_insensitive: true,
+};
+
+/// The base32 encoding with lowercase letters and without padding.
+pub(crate) const BASE32_LOWER_NOPAD: Encoding = Encoding {
+    padding: false,
+    ..BASE32_LOWER
+};
+
+/// The adapted base64 encoding used by PHC-style password hashes, with `.` instead of `+` and without padding.
+pub(crate) const AB64: Encoding = Encoding {
+    alphabet: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789./",
+    padding: false,
+    case_insensitive: false,
+};
+
+/// A binary-to-text encoding from the RFC 4648 family.
+pub(crate) struct Encoding {
+    alphabet: &'static [u8],
+    padding: bool,
+    case_insensitive: bool,
+}
+
+impl Encoding {
+    const PADDING: char = '=';
+
+    /// Returns the number of bits encoded by a single symbol.
+    const fn bits(&self) -> usize {
+        self.alphabet.len().trailing_zeros() as usize
+    }
+
+    /// Returns the length of the encoded data without padding.
+    const fn unpadded_length(&self, length: usize) -> usize {
+        (length * 8).div_ceil(self.bits())
+    }
+
+    /// Returns the length of the encoded data.
+    const fn length(&self, length: usize) -> usize {
+        let length = self.unpadded_length(length);
+        if self.padding {
+            // every block of symbols encodes a whole number of bytes
+            let block = if self.bits() == 6 { 4 } else { 8 };
+            length.div_ceil(block) * block
+        } else {
+            length
+        }
+    }
+
+    /// Encodes the digest.
+    pub(crate) fn encode(&self, digest: &Digest) -> String {
+        self.encode_bytes(digest.as_bytes())
+    }
+
+    /// Encodes bytes of arbitrary length.
+    pub(crate) fn encode_bytes(&self, bytes: &[u8]) -> String {
+        let bits = self.bits();
+        let mask = (1 << bits) - 1;
+        let length = self.length(bytes.len());
+        let mut encoded = String::with_capacity(length);
+        let mut buffer = 0usize;
+        let mut buffered = 0;
+        for &byte in bytes {
+            buffer = (buffer << 8) | usize::from(byte);
+            buffered += 8;
+            while buffered >= bits {
+                buffered -= bits;
+                encoded.push(char::from(self.alphabet[(buffer >> buffered) & mask]));
+            }
+            buffer &= (1 << buffered) - 1;
+        }
+        if buffered > 0 {
+            encoded.push(char::from(self.alphabet[(buffer << (bits - buffered)) & mask]));
+        }
+        while encoded.len() < length {
+            encoded.push(Self::PADDING);
+        }
+        encoded
+    }
+
+    /// Decodes the digest.
+    ///
+    /// The `offset` is added to the positions reported by [`FormatError`].
+    pub(crate) fn decode(&self, encoded: &str, offset: usize) -> Result<Digest, FormatError> {
+        let length = self.length(hash::DIGEST_LENGTH_BYTES);
+        let unpadded_length = self.unpadded_length(hash::DIGEST_LENGTH_BYTES);
+        if encoded.len() != length {
+            let error = FormatError::InvalidLength {
+                value: encoded.len() + offset,
+                proper: length + offset,
+            };
+            return Err(error);
+        }
+        let bits = self.bits();
+        let mut digest = [0u8; hash::DIGEST_LENGTH_BYTES];
+        let mut decoded = 0;
+        let mut buffer = 0usize;
+        let mut buffered = 0;
+        for (index, character) in encoded.char_indices() {
+            let invalid = FormatError::InvalidCharacter {
+                character,
+                index: index + offset,
+            };
+            if index >= unpadded_length {
+                if character != Self::PADDING {
+                    return Err(invalid);
+                }
+                continue;
+            }
+            let Some(symbol) = self.symbol(character) else {
+                return Err(invalid);
+            };
+            buffer = (buffer << bits) | symbol;
+            buffered += bits;
+            if buffered >= 8 {
+                buffered -= 8;
+                // the mask guarantees that the value fits into a byte
+                digest[decoded] = ((buffer >> buffered) & 0xFF) as u8;
+                decoded += 1;
+            }
+            buffer &= (1 << buffered) - 1;
+            if index + 1 == unpadded_length && buffer != 0 {
+                // the unused trailing bits must be zero in the canonical representation
+                return Err(invalid);
+            }
+        }
+        Ok(Digest::new(digest))
+    }
+
+    /// Decodes bytes of arbitrary length.
+    ///
+    /// The `offset` is added to the positions reported by [`FormatError`].
+    pub(crate) fn decode_bytes(&self, encoded: &str, offset: usize) -> Result<Vec<u8>, FormatError> {
+        let bits = self.bits();
+        let symbols = if self.padding {
+            encoded.trim_end_matches(Self::PADDING)
+        } else {
+            encoded
+        };
+        let mut bytes = Vec::with_capacity(symbols.len() * bits / 8);
+        let mut buffer = 0usize;
+        let mut buffered = 0;
+        for (index, character) in symbols.char_indices() {
+            let Some(symbol) = self.symbol(character) else {
+                let error = FormatError::InvalidCharacter {
+                    character,
+                    index: index + offset,
+                };
+                return Err(error);
+            };
+            buffer = (buffer << bits) | symbol;
+            buffered += bits;
+            if buffered >= 8 {
+                buffered -= 8;
+                // the mask guarantees that the value fits into a byte
+                bytes.push(((buffer >> buffered) & 0xFF) as u8);
+            }
+            buffer &= (1 << buffered) - 1;
+        }
+        // the last symbol must complete a byte and its unused trailing bits must be zero
+        if buffered >= bits || buffer != 0 {
+            if let Some((index, character)) = symbols.char_indices().last() {
+                let error = FormatError::InvalidCharacter {
+                    character,
+                    index: index + offset,
+                };
+                return Err(error);
+            }
+        }
+        let length = self.length(bytes.len());
+        if encoded.len() != length {
+            let error = FormatError::InvalidLength {
+                value: encoded.len() + offset,
+                proper: length + offset,
+            };
+            return Err(error);
+        }
+        Ok(bytes)
+    }
+
+    /// Returns the value of the symbol or `None` when the character doesn't belong to the alphabet.
+    fn symbol(&self, character: char) -> Option<usize> {
+        let character = if self.case_insensitive {
+            let lowercase = self.alphabet[0].is_ascii_lowercase();
+            if lowercase {
+                character.to_ascii_lowercase()
+            } else {
+                character.to_ascii_uppercase()
+            }
+        } else {
+            character
+        };
+        let character = u8::try_from(character).ok()?;
+        self.alphabet.iter().position(|&symbol| symbol == character)
+    }
+}
+
+/// A multibase encoding of the digest.
+///
+/// Multibase strings are prefixed with a single character which identifies the encoding of the rest of the string.
+/// See the [multibase specification](https://github.com/multiformats/multibase) for details.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+/// use sha2_224::Multibase;
+///
+/// let digest = sha2_224::hash(b"data");
+/// let encoded = digest.to_multibase(Multibase::Base64Url);
+/// assert_eq!(encoded, "u9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ");
+/// assert_eq!(
+///     sha2_224::Digest::try_from_multibase(&encoded).unwrap(),
+///     digest
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Multibase {
+    /// Lowercase hexadecimal, prefixed with `f`.
+    Base16,
+    /// Uppercase hexadecimal, prefixed with `F`.
+    Base16Upper,
+    /// Lowercase base32 without padding, prefixed with `b`.
+    Base32,
+    /// Uppercase base32 without padding, prefixed with `B`.
+    Base32Upper,
+    /// Lowercase base32 with padding, prefixed with `c`.
+    Base32Pad,
+    /// Uppercase base32 with padding, prefixed with `C`.
+    Base32PadUpper,
+    /// Standard base64 without padding, prefixed with `m`.
+    Base64,
+    /// Standard base64 with padding, prefixed with `M`.
+    Base64Pad,
+    /// URL-safe base64 without padding, prefixed with `u`.
+    Base64Url,
+    /// URL-safe base64 with padding, prefixed with `U`.
+    Base64UrlPad,
+}
+
+impl Multibase {
+    /// Returns the prefix character which identifies the encoding.
+    #[must_use]
+    pub const fn prefix(self) -> char {
+        match self {
+            Self::Base16 => 'f',
+            Self::Base16Upper => 'F',
+            Self::Base32 => 'b',
+            Self::Base32Upper => 'B',
+            Self::Base32Pad => 'c',
+            Self::Base32PadUpper => 'C',
+            Self::Base64 => 'm',
+            Self::Base64Pad => 'M',
+            Self::Base64Url => 'u',
+            Self::Base64UrlPad => 'U',
+        }
+    }
+
+    /// Returns the encoding identified by the prefix character or `None` when the encoding is not supported.
+    #[must_use]
+    pub const fn from_prefix(prefix: char) -> Option<Self> {
+        let base = match prefix {
+            'f' => Self::Base16,
+            'F' => Self::Base16Upper,
+            'b' => Self::Base32,
+            'B' => Self::Base32Upper,
+            'c' => Self::Base32Pad,
+            'C' => Self::Base32PadUpper,
+            'm' => Self::Base64,
+            'M' => Self::Base64Pad,
+            'u' => Self::Base64Url,
+            'U' => Self::Base64UrlPad,
+            _ => return None,
+        };
+        Some(base)
+    }
+
+    /// Encodes the digest without the prefix.
+    pub(crate) fn encode(self, digest: &Digest) -> String {
+        match self {
+            Self::Base16 => digest.to_hex_lowercase(),
+            Self::Base16Upper => digest.to_hex_uppercase(),
+            Self::Base32 => BASE32_LOWER_NOPAD.encode(digest),
+            Self::Base32Upper => BASE32_NOPAD.encode(digest),
+            Self::Base32Pad => BASE32_LOWER.encode(digest),
+            Self::Base32PadUpper => BASE32.encode(digest),
+            Self::Base64 => BASE64_NOPAD.encode(digest),
+            Self::Base64Pad => BASE64.encode(digest),
+            Self::Base64Url => BASE64_URL.encode(digest),
+            Self::Base64UrlPad => BASE64_URL_PAD.encode(digest),
+        }
+    }
+
+    /// Decodes the digest without the prefix.
+    pub(crate) fn decode(self, encoded: &str) -> Result<Digest, FormatError> {
+        let offset = self.prefix().len_utf8();
+        match self {
+            Self::Base16 | Self::Base16Upper => {
+                Digest::try_from_hex(encoded).map_err(|error| {
+                    match error {
+                        FormatError::InvalidLength { value, proper } => {
+                            FormatError::InvalidLength {
+                                value: value + offset,
+                                proper: proper + offset,
+                            }
+                        },
+                        FormatError::InvalidCharacter { character, index } => {
+                            FormatError::InvalidCharacter {
+                                character,
+                                index: index + offset,
+                            }
+                        },
+                        error => error,
+                    }
+                })
+            },
+            Self::Base32 => BASE32_LOWER_NOPAD.decode(encoded, offset),
+            Self::Base32Upper => BASE32_NOPAD.decode(encoded, offset),
+            Self::Base32Pad => BASE32_LOWER.decode(encoded, offset),
+            Self::Base32PadUpper => BASE32.decode(encoded, offset),
+            Self::Base64 => BASE64_NOPAD.decode(encoded, offset),
+            Self::Base64Pad => BASE64.decode(encoded, offset),
+            Self::Base64Url => BASE64_URL.decode(encoded, offset),
+            Self::Base64UrlPad => BASE64_URL_PAD.decode(encoded, offset),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! This module provides [HKDF](https://www.rfc-editor.org/rfc/rfc5869) key derivation built on top of the
+//! HMAC-SHA-2 224 message authentication code.
+//!
+//! The derivation consists of two steps. The [`extract`] step concentrates the entropy of the input keying material
+//! into a pseudorandom key, and the [`expand`] step derives output keying material of the requested length from it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha2_224 as sha2_224;
+//!
+//! # fn wrapper() -> Result<(), sha2_224::hkdf::Error> {
+//! let prk = sha2_224::hkdf::extract(b"salt", b"secret");
+//! let okm = sha2_224::hkdf::expand(&prk, b"info", 32)?;
+//! assert_eq!(okm.len(), 32);
+//! assert_eq!(
+//!     okm,
+//!     sha2_224::hkdf::derive(b"salt", b"secret", b"info", 32)?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::hmac::HmacSha224;
+use crate::{hash, Digest};
+
+/// The maximum length of the output keying material.
+pub const MAX_OUTPUT_LENGTH: usize = 255 * hash::DIGEST_LENGTH_BYTES;
+
+/// Extracts a pseudorandom key from the input keying material.
+///
+/// An empty salt is equivalent to a salt of [`DIGEST_LENGTH_BYTES`](hash::DIGEST_LENGTH_BYTES) zeros.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// let prk = sha2_224::hkdf::extract(b"salt", b"secret");
+/// assert_eq!(
+///     prk.to_hex_lowercase(),
+///     "944d17f27c97291763dc9c5ae157e4099e942b842d85e27865e9245a"
+/// );
+/// ```
+#[must_use]
+pub fn extract(salt: impl AsRef<[u8]>, ikm: impl AsRef<[u8]>) -> Digest {
+    // keys shorter than the block size are padded with zeros, so an empty salt needs no special treatment
+    crate::hmac::hmac(salt, ikm.as_ref())
+}
+
+/// Expands the pseudorandom key into output keying material of the given length.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidLength`] when the requested length exceeds [`MAX_OUTPUT_LENGTH`].
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// # fn wrapper() -> Result<(), sha2_224::hkdf::Error> {
+/// let prk = sha2_224::hkdf::extract(b"salt", b"secret");
+/// let okm = sha2_224::hkdf::expand(&prk, b"info", 1)?;
+/// assert_eq!(okm, [0x5C]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn expand(prk: impl AsRef<[u8]>, info: impl AsRef<[u8]>, length: usize) -> Result<Vec<u8>, Error> {
+    if length > MAX_OUTPUT_LENGTH {
+        let error = Error::InvalidLength {
+            value: length,
+            maximum: MAX_OUTPUT_LENGTH,
+        };
+        return Err(error);
+    }
+
+    let info = info.as_ref();
+    let hmac = HmacSha224::new(prk);
+    let mut okm = Vec::with_capacity(length);
+    let mut previous: Option<Digest> = None;
+    for counter in 1..=u8::MAX {
+        if okm.len() >= length {
+            break;
+        }
+        let mut hmac = hmac.clone();
+        if let Some(previous) = previous {
+            hmac.update(previous);
+        }
+        hmac.update(info);
+        hmac.update([counter]);
+        let block = hmac.finalize();
+        let remaining = length - okm.len();
+        okm.extend_from_slice(&block.as_bytes()[..remaining.min(hash::DIGEST_LENGTH_BYTES)]);
+        previous = Some(block);
+    }
+    Ok(okm)
+}
+
+/// Extracts a pseudorandom key from the input keying material and expands it into output keying material of the
+/// given length.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidLength`] when the requested length exceeds [`MAX_OUTPUT_LENGTH`].
+pub fn derive(
+    salt: impl AsRef<[u8]>,
+    ikm: impl AsRef<[u8]>,
+    info: impl AsRef<[u8]>,
+    length: usize,
+) -> Result<Vec<u8>, Error> {
+    let prk = extract(salt, ikm);
+    expand(prk, info, length)
+}
+
+/// An error type for the key derivation.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// Represents a requested output length above [`MAX_OUTPUT_LENGTH`].
+    #[error("Invalid output length `{value}`, maximum value `{maximum}`")]
+    InvalidLength { value: usize, maximum: usize },
+}
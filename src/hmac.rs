@@ -0,0 +1,221 @@
+//! This module provides [HMAC](https://www.rfc-editor.org/rfc/rfc2104) message authentication codes built on top of
+//! the SHA-2 224 hash function.
+//!
+//! Keys longer than the block size are hashed first, shorter keys are padded with zeros, exactly as specified by
+//! RFC 2104.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha2_224 as sha2_224;
+//! use sha2_224::hmac::HmacSha224;
+//!
+//! let mut hmac = HmacSha224::new(b"key");
+//! hmac.update(b"example ");
+//! hmac.update(b"data");
+//! let tag = hmac.finalize();
+//! assert_eq!(tag, sha2_224::hmac::hmac(b"key", b"example data"));
+//! assert!(hmac.verify(&tag));
+//! ```
+
+use std::fmt::{self, Debug, Formatter};
+
+use crate::{core, hash, Digest, Result, SHA2_224};
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5C;
+
+/// The HMAC-SHA-2 224 instance.
+#[derive(Clone)]
+pub struct HmacSha224 {
+    initial: SHA2_224,
+    inner: SHA2_224,
+    outer: SHA2_224,
+}
+
+impl HmacSha224 {
+    /// Creates a new instance keyed with the given key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224::hmac::HmacSha224;
+    ///
+    /// let mut hmac = HmacSha224::new(b"key");
+    /// hmac.update(b"data");
+    /// assert_eq!(
+    ///     hmac.finalize().to_hex_lowercase(),
+    ///     "19424d4210e50d7a4521b5f0d54b4b0cff3060deddccfd894fda5b3b"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        let key = key.as_ref();
+        let mut block = [0u8; hash::BLOCK_LENGTH_BYTES];
+        if key.len() > block.len() {
+            let digest = SHA2_224::hash(key);
+            block[..digest.as_bytes().len()].copy_from_slice(digest.as_bytes());
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut initial = SHA2_224::new();
+        initial.update(block.map(|byte| byte ^ IPAD));
+        let mut outer = SHA2_224::new();
+        outer.update(block.map(|byte| byte ^ OPAD));
+        let inner = initial.clone();
+        Self { initial, inner, outer }
+    }
+
+    /// Updates the state with an input data.
+    pub fn update<T>(&mut self, data: T)
+    where
+        T: AsRef<[u8]>,
+    {
+        self.inner.update(data);
+    }
+
+    /// Resets the state to its initial state, keeping the key.
+    pub fn reset(&mut self) {
+        self.inner = self.initial.clone();
+    }
+
+    /// Produces the message authentication code.
+    #[must_use]
+    pub fn finalize(&self) -> Digest {
+        let mut outer = self.outer.clone();
+        outer.update(self.inner.digest());
+        outer.digest()
+    }
+
+    /// Produces the message authentication code and compares it with the expected one in constant time.
+    #[must_use]
+    pub fn verify(&self, expected: &Digest) -> bool {
+        self.finalize().ct_eq(expected)
+    }
+}
+
+impl Debug for HmacSha224 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // the keyed states are as sensitive as the key itself
+        f.debug_struct("HmacSha224").finish_non_exhaustive()
+    }
+}
+
+/// An adaptor which feeds the keyed instance through the [`core::Hash`] trait.
+///
+/// It is private, because the trait requires [`Default`], which would allow computing codes with an empty key by
+/// mistake.
+struct Keyed(HmacSha224);
+
+impl Default for Keyed {
+    fn default() -> Self {
+        // never used, instances are always created with the key given by the caller
+        Self(HmacSha224::new([]))
+    }
+}
+
+impl core::Hash for Keyed {
+    type Digest = Digest;
+
+    fn update<T>(&mut self, data: T)
+    where
+        T: AsRef<[u8]>,
+    {
+        self.0.update(data);
+    }
+
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+
+    fn digest(&self) -> Self::Digest {
+        self.0.finalize()
+    }
+}
+
+/// Computes the message authentication code of the given input.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// let tag = sha2_224::hmac::hmac(b"key", b"data");
+/// assert_eq!(
+///     tag.to_hex_lowercase(),
+///     "19424d4210e50d7a4521b5f0d54b4b0cff3060deddccfd894fda5b3b"
+/// );
+/// ```
+#[must_use]
+pub fn hmac(key: impl AsRef<[u8]>, data: impl core::Hashable) -> Digest {
+    let mut hmac = Keyed(HmacSha224::new(key));
+    data.hash_with(&mut hmac);
+    hmac.0.finalize()
+}
+
+/// Computes the message authentication code of the given input.
+///
+/// # Errors
+///
+/// Returns [`Error`](crate::Error) when the input cannot be read.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// use chksum_sha2_224 as sha2_224;
+///
+/// # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+/// let file = File::open(path)?;
+/// let tag = sha2_224::hmac::chksum(b"key", file)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum(key: impl AsRef<[u8]>, mut data: impl core::Chksumable) -> Result<Digest> {
+    let mut hmac = Keyed(HmacSha224::new(key));
+    data.chksum_with(&mut hmac)?;
+    Ok(hmac.0.finalize())
+}
+
+/// Computes the message authentication code of the given input.
+///
+/// # Errors
+///
+/// Returns [`Error`](crate::Error) when the input cannot be read.
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(key: impl AsRef<[u8]>, mut data: impl core::AsyncChksumable) -> Result<Digest> {
+    let mut hmac = Keyed(HmacSha224::new(key));
+    data.chksum_with(&mut hmac).await?;
+    Ok(hmac.0.finalize())
+}
+
+/// Computes the message authentication code of the given input and compares it with the expected one in constant
+/// time.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// let tag = sha2_224::hmac::hmac(b"key", b"data");
+/// assert!(sha2_224::hmac::verify(b"key", b"data", &tag));
+/// assert!(!sha2_224::hmac::verify(b"other key", b"data", &tag));
+/// ```
+#[must_use]
+pub fn verify(key: impl AsRef<[u8]>, data: impl core::Hashable, expected: &Digest) -> bool {
+    hmac(key, data).ct_eq(expected)
+}
+
+/// Computes the message authentication code of the given input and compares it with the expected one in constant
+/// time.
+///
+/// # Errors
+///
+/// Returns [`Error`](crate::Error) when the input cannot be read.
+pub fn chksum_verify(key: impl AsRef<[u8]>, data: impl core::Chksumable, expected: &Digest) -> Result<bool> {
+    let digest = chksum(key, data)?;
+    Ok(digest.ct_eq(expected))
+}
@@ -239,6 +239,10 @@
 //!
 //! * `reader` enables the [`reader`] module with the [`Reader`] struct.
 //! * `writer` enables the [`writer`] module with the [`Writer`] struct.
+//! * `serde` enables serialization and deserialization of the [`Digest`] and [`state::State`] structs.
+//! * `cli` builds the `sha224sum` binary, a command-line utility compatible with its GNU coreutils counterpart.
+//! * `mmap` enables the [`chksum_mmap`] function and opt-in memory-mapped reading of large files by
+//!   [`chksum_file_with`] and in the [`dir`] and [`tree`] modules.
 //!
 //! By default, neither of these features is enabled.
 //!
@@ -266,14 +270,35 @@
 //! This crate is licensed under the MIT License.
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+#![cfg_attr(feature = "mmap", deny(unsafe_code))]
 
+mod cancel;
+pub mod checkfile;
+mod compression;
+pub mod dir;
+mod encoding;
+pub mod hkdf;
+pub mod hmac;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+mod options;
+pub mod pbkdf2;
 #[cfg(feature = "reader")]
 pub mod reader;
+pub mod sri;
+pub mod state;
+pub mod tree;
 #[cfg(feature = "writer")]
 pub mod writer;
 
+use std::cmp::Ordering;
 use std::fmt::{self, Display, Formatter, LowerHex, UpperHex};
+use std::hash::Hasher;
+use std::hint::black_box;
+use std::io::{self, Read};
+use std::result;
+use std::str::FromStr;
 
 use chksum_core as core;
 #[cfg(feature = "async-runtime-tokio")]
@@ -283,19 +308,32 @@ pub use chksum_core::AsyncChksumable;
 pub use chksum_core::{Chksumable, Error, Hash, Hashable, Result};
 #[doc(no_inline)]
 pub use chksum_hash_sha2_224 as hash;
+#[cfg(feature = "serde")]
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, Serializer};
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::AsyncRead;
 
+#[doc(inline)]
+pub use crate::cancel::{CancellationToken, Cancelled};
+#[doc(inline)]
+pub use crate::encoding::Multibase;
+#[doc(inline)]
+pub use crate::options::{ChksumOptions, Progress};
 #[cfg(all(feature = "reader", feature = "async-runtime-tokio"))]
 #[doc(inline)]
-pub use crate::reader::AsyncReader;
+pub use crate::reader::{AsyncReader, AsyncVerifyingReader};
 #[cfg(feature = "reader")]
 #[doc(inline)]
-pub use crate::reader::Reader;
+pub use crate::reader::{Reader, VerifyingReader};
+use crate::state::State;
 #[cfg(all(feature = "writer", feature = "async-runtime-tokio"))]
 #[doc(inline)]
-pub use crate::writer::AsyncWriter;
+pub use crate::writer::{AsyncVerifyingWriter, AsyncWriter};
 #[cfg(feature = "writer")]
 #[doc(inline)]
-pub use crate::writer::Writer;
+pub use crate::writer::{VerifyingFile, VerifyingWriter, Writer};
 
 /// Creates a new hash.
 ///
@@ -374,6 +412,112 @@ pub fn chksum(data: impl core::Chksumable) -> Result<Digest> {
     core::chksum::<SHA2_224>(data)
 }
 
+/// Computes the hash of the data read from the reader with the given options.
+///
+/// Unlike [`chksum`], the size of the read buffer can be chosen, the buffer can be reused between calls, and the
+/// progress can be reported.
+///
+/// # Errors
+///
+/// Returns [`Error`] when the reader fails.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// use chksum_sha2_224 as sha2_224;
+/// use sha2_224::ChksumOptions;
+///
+/// # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+/// let file = File::open(path)?;
+/// let mut options = ChksumOptions::new().buffer_size(256 * 1024);
+/// let digest = sha2_224::chksum_with(file, &mut options)?;
+/// assert_eq!(digest, sha2_224::chksum(path)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_with(reader: impl Read, options: &mut ChksumOptions<'_>) -> Result<Digest> {
+    options.chksum(reader)
+}
+
+/// Computes the hash of the file with the given options.
+///
+/// Unlike [`chksum_with`], the file can be mapped into memory when it is at least as long as the threshold set by
+/// `ChksumOptions::mmap_threshold`, which is available with the `mmap` feature.
+///
+/// # Errors
+///
+/// Returns [`Error`] when the file cannot be opened or read.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha2_224 as sha2_224;
+/// use sha2_224::ChksumOptions;
+///
+/// # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+/// let mut options = ChksumOptions::new();
+/// let digest = sha2_224::chksum_file_with(path, &mut options)?;
+/// assert_eq!(digest, sha2_224::chksum(path)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_file_with(path: impl AsRef<std::path::Path>, options: &mut ChksumOptions<'_>) -> Result<Digest> {
+    let file = std::fs::File::open(path)?;
+    options.chksum_file(file)
+}
+
+/// Computes the hash of the data read from the asynchronous reader with the given options.
+///
+/// # Errors
+///
+/// Returns [`Error`] when the reader fails.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha2_224 as sha2_224;
+/// use sha2_224::ChksumOptions;
+/// use tokio::fs::File;
+///
+/// # async fn wrapper(path: &Path) -> sha2_224::Result<()> {
+/// let file = File::open(path).await?;
+/// let mut options = ChksumOptions::new().buffer_size(256 * 1024);
+/// let digest = sha2_224::async_chksum_with(file, &mut options).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_with(reader: impl AsyncRead + Unpin, options: &mut ChksumOptions<'_>) -> Result<Digest> {
+    options.async_chksum(reader).await
+}
+
+/// Computes the hash of the file by mapping it into memory.
+///
+/// Pipes, special files and empty files are read with buffered I/O, the same happens when the file cannot be mapped.
+/// The process crashes when the file is truncated while it is hashed, check [`mmap`] module for details.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha2_224 as sha2_224;
+///
+/// # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+/// let digest = sha2_224::chksum_mmap(path)?;
+/// assert_eq!(digest, sha2_224::chksum(path)?);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "mmap")]
+pub fn chksum_mmap(path: impl AsRef<std::path::Path>) -> Result<Digest> {
+    mmap::chksum(path)
+}
+
 /// Computes the hash of the given input.
 ///
 /// # Example
@@ -396,10 +540,102 @@ pub async fn async_chksum(data: impl core::AsyncChksumable) -> Result<Digest> {
     core::async_chksum::<SHA2_224>(data).await
 }
 
+/// Computes the hash of the given input and compares it with the expected digest in constant time.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// let expected =
+///     sha2_224::Digest::try_from_hex("90382cbfda2656313ad61fd74b32ddfa4bcc118f660bd4fba9228ced")
+///         .unwrap();
+/// assert!(sha2_224::verify(b"example data", &expected));
+/// ```
+#[must_use]
+pub fn verify(data: impl core::Hashable, expected: &Digest) -> bool {
+    hash(data).ct_eq(expected)
+}
+
+/// Computes the hash of the given input and compares it with the expected digest in constant time.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_sha2_224::Result;
+/// use chksum_sha2_224 as sha2_224;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let expected =
+///     sha2_224::Digest::try_from_hex("90382cbfda2656313ad61fd74b32ddfa4bcc118f660bd4fba9228ced")
+///         .unwrap();
+/// let file = File::open(path)?;
+/// assert!(sha2_224::chksum_verify(file, &expected)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_verify(data: impl core::Chksumable, expected: &Digest) -> Result<bool> {
+    let digest = chksum(data)?;
+    Ok(digest.ct_eq(expected))
+}
+
+/// Computes the hash of the given input and compares it with the expected digest in constant time.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// # async fn wrapper() -> sha2_224::Result<()> {
+/// let expected =
+///     sha2_224::Digest::try_from_hex("90382cbfda2656313ad61fd74b32ddfa4bcc118f660bd4fba9228ced")
+///         .unwrap();
+/// assert!(sha2_224::async_chksum_verify(b"example data", &expected).await?);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_verify(data: impl core::AsyncChksumable, expected: &Digest) -> Result<bool> {
+    let digest = async_chksum(data).await?;
+    Ok(digest.ct_eq(expected))
+}
+
+/// Compares two byte slices in constant time with respect to their contents.
+///
+/// Slices of different lengths are never equal, the lengths themselves are not considered secret.
+pub(crate) fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+    let difference = left
+        .iter()
+        .zip(right)
+        .fold(0u8, |difference, (left, right)| difference | black_box(left ^ right));
+    black_box(difference) == 0
+}
+
 /// The SHA-2 224 hash instance.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct SHA2_224 {
-    inner: hash::Update,
+    inner: Engine,
+}
+
+/// The implementation behind a hash instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Engine {
+    /// The upstream implementation, which keeps its state private.
+    Update(hash::Update),
+    /// The exportable state, check [`state`] module.
+    State(State),
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::Update(hash::Update::new())
+    }
 }
 
 impl SHA2_224 {
@@ -444,10 +680,30 @@ impl SHA2_224 {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        let inner = hash::Update::new();
+        let inner = Engine::Update(hash::Update::new());
         Self { inner }
     }
 
+    /// Creates a new hash whose state can be exported with [`SHA2_224::export_state`].
+    ///
+    /// The upstream implementation used by [`SHA2_224::new`] doesn't expose its state, so resumable hashes use the
+    /// compression function of this crate, which is slower. Use them only when the state has to be exported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224::SHA2_224;
+    ///
+    /// let mut hash = SHA2_224::resumable();
+    /// hash.update(b"example data");
+    /// assert_eq!(hash.digest(), SHA2_224::hash(b"example data"));
+    /// assert!(hash.export_state().is_some());
+    /// ```
+    #[must_use]
+    pub fn resumable() -> Self {
+        Self::from_state(State::default())
+    }
+
     /// Updates the hash state with an input data.
     ///
     /// # Example
@@ -469,7 +725,12 @@ impl SHA2_224 {
     where
         T: AsRef<[u8]>,
     {
-        self.inner.update(data);
+        match &mut self.inner {
+            Engine::Update(inner) => {
+                inner.update(data);
+            },
+            Engine::State(inner) => inner.update(data.as_ref()),
+        }
     }
 
     /// Resets the hash state to its initial state.
@@ -489,7 +750,12 @@ impl SHA2_224 {
     /// );
     /// ```
     pub fn reset(&mut self) {
-        self.inner.reset();
+        match &mut self.inner {
+            Engine::Update(inner) => {
+                inner.reset();
+            },
+            Engine::State(inner) => inner.reset(),
+        }
     }
 
     /// Produces the hash digest.
@@ -508,7 +774,48 @@ impl SHA2_224 {
     /// ```
     #[must_use]
     pub fn digest(&self) -> Digest {
-        self.inner.digest().into()
+        match &self.inner {
+            Engine::Update(inner) => inner.digest().into(),
+            Engine::State(inner) => inner.digest(),
+        }
+    }
+
+    /// Exports the hash state, which allows to resume the computation later.
+    ///
+    /// Returns `None` for hashes created by [`SHA2_224::new`], only hashes created by [`SHA2_224::resumable`] and
+    /// [`SHA2_224::from_state`] can be exported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224::SHA2_224;
+    ///
+    /// let mut hash = SHA2_224::resumable();
+    /// hash.update(b"example ");
+    /// let state = hash.export_state().unwrap();
+    ///
+    /// let mut hash = SHA2_224::from_state(state);
+    /// hash.update(b"data");
+    /// assert_eq!(
+    ///     hash.digest().to_hex_lowercase(),
+    ///     "90382cbfda2656313ad61fd74b32ddfa4bcc118f660bd4fba9228ced"
+    /// );
+    ///
+    /// assert_eq!(SHA2_224::new().export_state(), None);
+    /// ```
+    #[must_use]
+    pub fn export_state(&self) -> Option<State> {
+        match &self.inner {
+            Engine::Update(_) => None,
+            Engine::State(inner) => Some(inner.clone()),
+        }
+    }
+
+    /// Creates a resumable hash which continues the computation from the exported state.
+    #[must_use]
+    pub fn from_state(state: State) -> Self {
+        let inner = Engine::State(state);
+        Self { inner }
     }
 }
 
@@ -532,6 +839,7 @@ impl core::Hash for SHA2_224 {
 }
 
 /// A hash digest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Digest(hash::Digest);
 
 impl Digest {
@@ -542,6 +850,101 @@ impl Digest {
         Self(inner)
     }
 
+    /// Creates a digest from a string in the hexadecimal representation.
+    ///
+    /// Both lowercase and uppercase digits are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError`] when the string length is different than [`LENGTH_HEX`](hash::digest::LENGTH_HEX) or
+    /// when it contains a character that is not a hexadecimal digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    ///
+    /// let digest =
+    ///     sha2_224::Digest::try_from_hex("D14A028C2A3A2BC9476102BB288234C415A2B01F828EA62AC5B3E42F")
+    ///         .unwrap();
+    /// assert_eq!(
+    ///     digest.to_hex_lowercase(),
+    ///     "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"
+    /// );
+    /// ```
+    pub fn try_from_hex(digest: &str) -> result::Result<Self, FormatError> {
+        if digest.len() != hash::digest::LENGTH_HEX {
+            let error = FormatError::InvalidLength {
+                value: digest.len(),
+                proper: hash::digest::LENGTH_HEX,
+            };
+            return Err(error);
+        }
+        let mut bytes = [0u8; hash::DIGEST_LENGTH_BYTES];
+        for (index, character) in digest.char_indices() {
+            let nibble = character
+                .to_digit(16)
+                .ok_or(FormatError::InvalidCharacter { character, index })?;
+            // hexadecimal digits are single-byte characters so the index is also a nibble index
+            let byte = &mut bytes[index / 2];
+            *byte = (*byte << 4) | nibble as u8;
+        }
+        let digest = Self::new(bytes);
+        Ok(digest)
+    }
+
+    /// Compares two digests in constant time.
+    ///
+    /// Unlike the `==` operator, the comparison doesn't stop at the first differing byte, so the time it takes
+    /// doesn't reveal how many leading bytes of the digests are equal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    ///
+    /// let digest = sha2_224::hash(b"example data");
+    /// let expected =
+    ///     sha2_224::Digest::try_from_hex("90382cbfda2656313ad61fd74b32ddfa4bcc118f660bd4fba9228ced")
+    ///         .unwrap();
+    /// assert!(digest.ct_eq(&expected));
+    /// ```
+    #[must_use]
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        constant_time_eq(self.as_bytes(), other.as_bytes())
+    }
+
+    /// Compares the digest with the expected one in constant time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VerifyError::Mismatch`] when the digests are different.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    ///
+    /// # fn wrapper() -> Result<(), sha2_224::VerifyError> {
+    /// let expected =
+    ///     sha2_224::Digest::try_from_hex("90382cbfda2656313ad61fd74b32ddfa4bcc118f660bd4fba9228ced")
+    ///         .unwrap();
+    /// sha2_224::chksum(b"example data")?.verify(&expected)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify(&self, expected: &Self) -> result::Result<(), VerifyError> {
+        if self.ct_eq(expected) {
+            Ok(())
+        } else {
+            let error = VerifyError::Mismatch {
+                expected: *expected,
+                actual: *self,
+            };
+            Err(error)
+        }
+    }
+
     /// Returns a byte slice of the digest's contents.
     #[must_use]
     pub const fn as_bytes(&self) -> &[u8] {
@@ -613,6 +1016,168 @@ impl Digest {
         let Self(inner) = self;
         inner.to_hex_uppercase()
     }
+
+    /// Returns a string in the standard base64 representation with padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    ///
+    /// let digest = sha2_224::hash(b"");
+    /// assert_eq!(
+    ///     digest.to_base64(),
+    ///     "0UoCjCo6K8lHYQK7KII0xBWisB+CjqYqxbPkLw=="
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_base64(&self) -> String {
+        encoding::BASE64.encode(self)
+    }
+
+    /// Creates a digest from a string in the standard base64 representation with padding.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError`] when the string is not a valid base64 representation of a digest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    ///
+    /// let digest =
+    ///     sha2_224::Digest::try_from_base64("0UoCjCo6K8lHYQK7KII0xBWisB+CjqYqxbPkLw==").unwrap();
+    /// assert_eq!(digest, sha2_224::hash(b""));
+    /// ```
+    pub fn try_from_base64(digest: &str) -> result::Result<Self, FormatError> {
+        encoding::BASE64.decode(digest, 0)
+    }
+
+    /// Returns a string in the URL-safe base64 representation without padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    ///
+    /// let digest = sha2_224::hash(b"");
+    /// assert_eq!(
+    ///     digest.to_base64_url(),
+    ///     "0UoCjCo6K8lHYQK7KII0xBWisB-CjqYqxbPkLw"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_base64_url(&self) -> String {
+        encoding::BASE64_URL.encode(self)
+    }
+
+    /// Creates a digest from a string in the URL-safe base64 representation without padding.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError`] when the string is not a valid URL-safe base64 representation of a digest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    ///
+    /// let digest =
+    ///     sha2_224::Digest::try_from_base64_url("0UoCjCo6K8lHYQK7KII0xBWisB-CjqYqxbPkLw").unwrap();
+    /// assert_eq!(digest, sha2_224::hash(b""));
+    /// ```
+    pub fn try_from_base64_url(digest: &str) -> result::Result<Self, FormatError> {
+        encoding::BASE64_URL.decode(digest, 0)
+    }
+
+    /// Returns a string in the base32 representation with uppercase letters and padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    ///
+    /// let digest = sha2_224::hash(b"");
+    /// assert_eq!(
+    ///     digest.to_base32(),
+    ///     "2FFAFDBKHIV4SR3BAK5SRARUYQK2FMA7QKHKMKWFWPSC6==="
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_base32(&self) -> String {
+        encoding::BASE32.encode(self)
+    }
+
+    /// Creates a digest from a string in the base32 representation with padding.
+    ///
+    /// Both lowercase and uppercase letters are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError`] when the string is not a valid base32 representation of a digest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    ///
+    /// let digest =
+    ///     sha2_224::Digest::try_from_base32("2FFAFDBKHIV4SR3BAK5SRARUYQK2FMA7QKHKMKWFWPSC6===")
+    ///         .unwrap();
+    /// assert_eq!(digest, sha2_224::hash(b""));
+    /// ```
+    pub fn try_from_base32(digest: &str) -> result::Result<Self, FormatError> {
+        encoding::BASE32.decode(digest, 0)
+    }
+
+    /// Returns a string in the given multibase representation, including the prefix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    /// use sha2_224::Multibase;
+    ///
+    /// let digest = sha2_224::hash(b"");
+    /// assert_eq!(
+    ///     digest.to_multibase(Multibase::Base16),
+    ///     "fd14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_multibase(&self, base: Multibase) -> String {
+        let mut encoded = String::from(base.prefix());
+        encoded.push_str(&base.encode(self));
+        encoded
+    }
+
+    /// Creates a digest from a string in the multibase representation.
+    ///
+    /// The encoding is chosen according to the prefix, see [`Multibase`] for supported encodings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError`] when the prefix is missing or unknown, or when the rest of the string is not a valid
+    /// representation of a digest in the chosen encoding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224 as sha2_224;
+    ///
+    /// let digest =
+    ///     sha2_224::Digest::try_from_multibase("m0UoCjCo6K8lHYQK7KII0xBWisB+CjqYqxbPkLw").unwrap();
+    /// assert_eq!(digest, sha2_224::hash(b""));
+    /// ```
+    pub fn try_from_multibase(digest: &str) -> result::Result<Self, FormatError> {
+        let mut characters = digest.chars();
+        let prefix = characters.next();
+        let base = prefix
+            .and_then(Multibase::from_prefix)
+            .ok_or(FormatError::UnknownBase { prefix })?;
+        base.decode(characters.as_str())
+    }
 }
 
 impl core::Digest for Digest {}
@@ -624,6 +1189,42 @@ impl AsRef<[u8]> for Digest {
     }
 }
 
+impl std::hash::Hash for Digest {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl PartialOrd for Digest {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Digest {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl PartialEq<[u8]> for Digest {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
+    }
+}
+
+impl PartialEq<&str> for Digest {
+    /// Compares the digest with a string in the hexadecimal representation, ignoring the case of the digits.
+    ///
+    /// Strings which cannot be parsed as a digest are never equal.
+    fn eq(&self, other: &&str) -> bool {
+        Self::try_from_hex(other).is_ok_and(|other| *self == other)
+    }
+}
+
 impl Display for Digest {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let Self(inner) = self;
@@ -656,3 +1257,134 @@ impl From<hash::Digest> for Digest {
         Self(digest)
     }
 }
+
+impl FromStr for Digest {
+    type Err = FormatError;
+
+    fn from_str(digest: &str) -> result::Result<Self, Self::Err> {
+        Self::try_from_hex(digest)
+    }
+}
+
+impl TryFrom<&str> for Digest {
+    type Error = FormatError;
+
+    fn try_from(digest: &str) -> result::Result<Self, Self::Error> {
+        Self::try_from_hex(digest)
+    }
+}
+
+/// Serializes the digest as a lowercase hexadecimal string in human-readable formats and as raw bytes otherwise.
+#[cfg(feature = "serde")]
+impl Serialize for Digest {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_hex_lowercase())
+        } else {
+            serializer.serialize_bytes(self.as_bytes())
+        }
+    }
+}
+
+/// Deserializes the digest from a hexadecimal string in human-readable formats and from raw bytes otherwise.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Digest {
+    fn deserialize<D>(deserializer: D) -> result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct DigestVisitor;
+
+        impl<'de> Visitor<'de> for DigestVisitor {
+            type Value = Digest;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                write!(formatter, "a hexadecimal string or {} bytes", hash::DIGEST_LENGTH_BYTES)
+            }
+
+            fn visit_str<E>(self, digest: &str) -> result::Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Digest::try_from_hex(digest).map_err(E::custom)
+            }
+
+            fn visit_bytes<E>(self, digest: &[u8]) -> result::Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let digest = digest.try_into().map_err(|_| E::invalid_length(digest.len(), &self))?;
+                Ok(Digest::new(digest))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> result::Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut digest = [0u8; hash::DIGEST_LENGTH_BYTES];
+                for (index, byte) in digest.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(index, &self))?;
+                }
+                if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    return Err(de::Error::invalid_length(hash::DIGEST_LENGTH_BYTES + 1, &self));
+                }
+                Ok(Digest::new(digest))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(DigestVisitor)
+        } else {
+            deserializer.deserialize_bytes(DigestVisitor)
+        }
+    }
+}
+
+/// An error type for the digest parsing.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum FormatError {
+    /// Represents an invalid length error with detailed information.
+    #[error("Invalid length `{value}`, proper value `{proper}`")]
+    InvalidLength { value: usize, proper: usize },
+    /// Represents a character which is not allowed at the given position.
+    #[error("Invalid character `{character}` at position `{index}`")]
+    InvalidCharacter { character: char, index: usize },
+    /// Represents a missing or unsupported multibase prefix.
+    #[error("Unknown multibase prefix")]
+    UnknownBase { prefix: Option<char> },
+}
+
+/// An error type for the digest verification.
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+    /// The calculated digest is different than the expected one.
+    #[error("Digest mismatch")]
+    Mismatch { expected: Digest, actual: Digest },
+    /// The digest cannot be calculated.
+    #[error(transparent)]
+    Chksum(#[from] Error),
+}
+
+impl VerifyError {
+    /// Returns the verification error wrapped by the [`io::Error`], if any.
+    ///
+    /// Verifying readers and writers report a digest mismatch as an [`io::Error`] of [`io::ErrorKind::InvalidData`]
+    /// kind which wraps [`VerifyError::Mismatch`].
+    #[must_use]
+    pub fn from_io_error(error: &io::Error) -> Option<&Self> {
+        error.get_ref().and_then(|error| error.downcast_ref())
+    }
+}
+
+/// Compares the digest of the hash with the expected one, failing with an [`io::Error`] on mismatch.
+#[cfg(any(feature = "reader", feature = "writer"))]
+fn verify_io(hash: &SHA2_224, expected: &Digest) -> io::Result<()> {
+    hash.digest()
+        .verify(expected)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
@@ -0,0 +1,67 @@
+//! This module provides hashing of memory-mapped files.
+//!
+//! Mapping a file into memory avoids copying its contents through a buffer, which makes hashing of large files
+//! faster. Files are mapped read-only, and only regular files are mapped; pipes, special files and empty files, as well
+//! as files which cannot be mapped, are read with buffered I/O instead.
+//!
+//! Besides [`chksum`], files can be mapped by [`chksum_file_with`](crate::chksum_file_with),
+//! [`DirHasher`](crate::dir::DirHasher) and [`TreeHasher::chksum`](crate::tree::TreeHasher::chksum) when it is
+//! enabled with their `mmap_threshold` methods, for which [`THRESHOLD`] is a reasonable value. Plain
+//! [`chksum`](crate::chksum) never maps files, because hashing of paths and files is implemented by the `chksum-core`
+//! crate.
+//!
+//! # Safety
+//!
+//! A mapped file must not be modified by other processes while it is hashed. When the file is truncated, reading the
+//! pages past its new end raises the `SIGBUS` signal on Unix, or an access violation on Windows, which terminates the
+//! whole process. Other modifications make the digest unspecified. Don't map files which may change, e.g. files of a
+//! working tree which is edited at the same time. This is why mapping is never enabled by default, and must stay an
+//! explicit choice of the caller.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use chksum_sha2_224 as sha2_224;
+//!
+//! # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+//! let digest = sha2_224::mmap::chksum(path)?;
+//! assert_eq!(digest, sha2_224::chksum(path)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::{Digest, Result};
+
+/// The recommended minimal length of a mapped file, smaller files are read faster with buffered I/O.
+pub const THRESHOLD: u64 = 1024 * 1024;
+
+/// Computes the hash of the file by mapping it into memory.
+///
+/// # Errors
+///
+/// Returns [`Error`](crate::Error) when the file cannot be opened or read.
+pub fn chksum(path: impl AsRef<Path>) -> Result<Digest> {
+    let file = File::open(path)?;
+    match map(&file, 0) {
+        Some(map) => Ok(crate::hash(&map[..])),
+        None => crate::chksum(file),
+    }
+}
+
+/// Maps the file when it is a non-empty regular file of at least the given length.
+#[allow(unsafe_code)]
+pub(crate) fn map(file: &File, threshold: u64) -> Option<Mmap> {
+    let metadata = file.metadata().ok()?;
+    if !metadata.is_file() || metadata.len() == 0 || metadata.len() < threshold {
+        return None;
+    }
+    // SAFETY: the map is read-only and never outlives the file, truncation by other processes is documented in the
+    // module as crashing the process, and mapping is always chosen explicitly by the caller
+    unsafe { Mmap::map(file) }.ok()
+}
@@ -0,0 +1,318 @@
+use std::fmt::{self, Debug, Formatter};
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use std::path::Path;
+
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{CancellationToken, Digest, Result, SHA2_224};
+
+/// A function which receives progress reports, shared by all hashing operations.
+pub(crate) type ProgressFn<'a> = dyn FnMut(&Progress<'_>) + Send + 'a;
+
+/// A callback which receives progress reports.
+type Callback<'a> = Box<ProgressFn<'a>>;
+
+/// Options of hashing with [`chksum_with`](crate::chksum_with), [`chksum_file_with`](crate::chksum_file_with) and
+/// [`async_chksum_with`](crate::async_chksum_with).
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// use chksum_sha2_224 as sha2_224;
+/// use sha2_224::ChksumOptions;
+///
+/// # fn wrapper(paths: &[&Path]) -> sha2_224::Result<()> {
+/// let mut buffer = vec![0u8; 1024 * 1024];
+/// for path in paths {
+///     let file = File::open(path)?;
+///     let mut options = ChksumOptions::new().buffer(&mut buffer);
+///     let digest = sha2_224::chksum_with(file, &mut options)?;
+///     assert_eq!(digest, sha2_224::chksum(*path)?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ChksumOptions<'a> {
+    buffer: Buffer<'a>,
+    progress: Option<Callback<'a>>,
+    total: Option<u64>,
+    cancellation: Option<CancellationToken>,
+    #[cfg(feature = "mmap")]
+    mmap_threshold: Option<u64>,
+}
+
+impl<'a> ChksumOptions<'a> {
+    /// The default size of the read buffer, the same as used by [`chksum`](crate::chksum).
+    pub const DEFAULT_BUFFER_SIZE: usize = 8 * 1024;
+
+    /// Creates new options with a buffer of [`DEFAULT_BUFFER_SIZE`](Self::DEFAULT_BUFFER_SIZE) bytes.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            buffer: Buffer::Owned(vec![0; Self::DEFAULT_BUFFER_SIZE]),
+            progress: None,
+            total: None,
+            cancellation: None,
+            #[cfg(feature = "mmap")]
+            mmap_threshold: None,
+        }
+    }
+
+    /// Sets the size of the read buffer, which is allocated once and reused while the options live.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the size is zero.
+    #[must_use]
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        assert!(size > 0, "buffer size must be greater than zero");
+        self.buffer = Buffer::Owned(vec![0; size]);
+        self
+    }
+
+    /// Sets the caller-provided read buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the buffer is empty.
+    #[must_use]
+    pub fn buffer(mut self, buffer: &'a mut [u8]) -> Self {
+        assert!(!buffer.is_empty(), "buffer must not be empty");
+        self.buffer = Buffer::Borrowed(buffer);
+        self
+    }
+
+    /// Sets the callback which is called after every chunk of the input is hashed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// use std::fs::File;
+    ///
+    /// use chksum_sha2_224 as sha2_224;
+    /// use sha2_224::ChksumOptions;
+    ///
+    /// # fn wrapper(path: &Path) -> sha2_224::Result<()> {
+    /// let file = File::open(path)?;
+    /// let mut options = ChksumOptions::new()
+    ///     .total(file.metadata()?.len())
+    ///     .progress(|progress| {
+    ///         if let Some(total) = progress.total() {
+    ///             println!("{} / {total} bytes", progress.processed());
+    ///         }
+    ///     });
+    /// let digest = sha2_224::chksum_with(file, &mut options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn progress(mut self, callback: impl FnMut(&Progress<'_>) + Send + 'a) -> Self {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the total length of the input reported to the progress callback, e.g. the length of a file.
+    #[must_use]
+    pub fn total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Sets the token which cancels hashing, it is checked before every chunk of the input is read.
+    #[must_use]
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Sets the minimal length of files which are mapped into memory by [`chksum_file_with`](crate::chksum_file_with),
+    /// `None`, the default, disables mapping.
+    ///
+    /// Check the [safety notes](crate::mmap#safety) before enabling it.
+    #[cfg(feature = "mmap")]
+    #[must_use]
+    pub fn mmap_threshold(mut self, threshold: Option<u64>) -> Self {
+        self.mmap_threshold = threshold;
+        self
+    }
+
+    /// Hashes the file, mapping it into memory when it is enabled and the file is long enough.
+    pub(crate) fn chksum_file(&mut self, file: File) -> Result<Digest> {
+        #[cfg(feature = "mmap")]
+        if let Some(map) = self
+            .mmap_threshold
+            .and_then(|threshold| crate::mmap::map(&file, threshold))
+        {
+            return self.chksum_map(&map);
+        }
+        self.chksum(file)
+    }
+
+    /// Hashes the mapped file in chunks of the buffer length, so progress and cancellation work like with reads.
+    #[cfg(feature = "mmap")]
+    fn chksum_map(&mut self, map: &[u8]) -> Result<Digest> {
+        let Self {
+            buffer,
+            progress,
+            total,
+            cancellation,
+            ..
+        } = self;
+        let mut hash = SHA2_224::new();
+        let mut processed = 0;
+        for chunk in map.chunks(buffer.as_mut().len()) {
+            if let Some(cancellation) = cancellation {
+                cancellation.check()?;
+            }
+            hash.update(chunk);
+            processed += chunk.len() as u64;
+            report(progress, processed, *total);
+        }
+        Ok(hash.digest())
+    }
+
+    /// Hashes the data read from the reader.
+    pub(crate) fn chksum(&mut self, mut reader: impl Read) -> Result<Digest> {
+        let Self {
+            buffer,
+            progress,
+            total,
+            cancellation,
+            ..
+        } = self;
+        let buffer = buffer.as_mut();
+        let mut hash = SHA2_224::new();
+        let mut processed = 0;
+        loop {
+            if let Some(cancellation) = cancellation {
+                cancellation.check()?;
+            }
+            match reader.read(buffer) {
+                Ok(0) => break,
+                Ok(length) => {
+                    hash.update(&buffer[..length]);
+                    processed += length as u64;
+                    report(progress, processed, *total);
+                },
+                Err(error) if error.kind() == ErrorKind::Interrupted => {},
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Ok(hash.digest())
+    }
+
+    /// Hashes the data read from the asynchronous reader.
+    #[cfg(feature = "async-runtime-tokio")]
+    pub(crate) async fn async_chksum(&mut self, mut reader: impl AsyncRead + Unpin) -> Result<Digest> {
+        let Self {
+            buffer,
+            progress,
+            total,
+            cancellation,
+            ..
+        } = self;
+        let buffer = buffer.as_mut();
+        let mut hash = SHA2_224::new();
+        let mut processed = 0;
+        loop {
+            if let Some(cancellation) = cancellation {
+                cancellation.check()?;
+            }
+            match reader.read(buffer).await {
+                Ok(0) => break,
+                Ok(length) => {
+                    hash.update(&buffer[..length]);
+                    processed += length as u64;
+                    report(progress, processed, *total);
+                },
+                Err(error) if error.kind() == ErrorKind::Interrupted => {},
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Ok(hash.digest())
+    }
+}
+
+impl Debug for ChksumOptions<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let buffer_size = match &self.buffer {
+            Buffer::Owned(buffer) => buffer.len(),
+            Buffer::Borrowed(buffer) => buffer.len(),
+        };
+        f.debug_struct("ChksumOptions")
+            .field("buffer_size", &buffer_size)
+            .field("total", &self.total)
+            .field("cancellation", &self.cancellation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ChksumOptions<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A read buffer.
+enum Buffer<'a> {
+    /// A buffer allocated by the options.
+    Owned(Vec<u8>),
+    /// A buffer provided by the caller.
+    Borrowed(&'a mut [u8]),
+}
+
+impl AsMut<[u8]> for Buffer<'_> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        match self {
+            Self::Owned(buffer) => buffer,
+            Self::Borrowed(buffer) => buffer,
+        }
+    }
+}
+
+/// Calls the callback, if any, with a report without a path.
+fn report(progress: &mut Option<Callback<'_>>, processed: u64, total: Option<u64>) {
+    if let Some(progress) = progress {
+        progress(&Progress::new(processed, total, None));
+    }
+}
+
+/// A progress report of a hashing operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress<'a> {
+    processed: u64,
+    total: Option<u64>,
+    path: Option<&'a Path>,
+}
+
+impl<'a> Progress<'a> {
+    pub(crate) fn new(processed: u64, total: Option<u64>, path: Option<&'a Path>) -> Self {
+        Self { processed, total, path }
+    }
+
+    /// Returns the number of bytes hashed so far.
+    #[must_use]
+    pub fn processed(&self) -> u64 {
+        self.processed
+    }
+
+    /// Returns the total number of bytes when it is known.
+    ///
+    /// The total comes from file lengths, so it may be inaccurate when files are modified during hashing.
+    #[must_use]
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Returns the path of the file being hashed during directory walks.
+    #[must_use]
+    pub fn path(&self) -> Option<&'a Path> {
+        self.path
+    }
+}
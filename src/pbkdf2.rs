@@ -0,0 +1,199 @@
+//! This module provides [PBKDF2](https://www.rfc-editor.org/rfc/rfc8018#section-5.2) key derivation and password
+//! hashing built on top of the HMAC-SHA-2 224 message authentication code.
+//!
+//! Password hashes are stored as PHC-style strings of the form `$pbkdf2-sha224$<rounds>$<salt>$<hash>`, where the
+//! number of rounds is a decimal number, and the salt and the hash are encoded in the adapted base64 encoding, which
+//! uses `.` instead of `+` and omits padding. The format follows the layout of the `pbkdf2_*` hashes of the passlib
+//! Python library, which doesn't provide a SHA-2 224 variant itself.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha2_224 as sha2_224;
+//! use sha2_224::pbkdf2::PasswordHash;
+//!
+//! # fn wrapper() -> Result<(), sha2_224::pbkdf2::Error> {
+//! let hash = PasswordHash::new(b"password", b"random salt", 29_000)?;
+//! let stored = hash.to_string();
+//!
+//! assert!(sha2_224::pbkdf2::verify(b"password", &stored)?);
+//! assert!(!sha2_224::pbkdf2::verify(b"wrong password", &stored)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::encoding::AB64;
+use crate::hmac::HmacSha224;
+use crate::{constant_time_eq, hash, FormatError};
+
+/// The identifier of the algorithm in PHC-style strings.
+pub const IDENTIFIER: &str = "pbkdf2-sha224";
+
+/// Derives a key of the given length from the password.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidRounds`] when the number of rounds is zero and [`Error::InvalidLength`] when the requested
+/// length exceeds `(2^32 - 1) * 28` bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// # fn wrapper() -> Result<(), sha2_224::pbkdf2::Error> {
+/// let key = sha2_224::pbkdf2::derive(b"password", b"salt", 1, 4)?;
+/// assert_eq!(key, [0x3C, 0x19, 0x8C, 0xBD]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn derive(
+    password: impl AsRef<[u8]>,
+    salt: impl AsRef<[u8]>,
+    rounds: u32,
+    length: usize,
+) -> Result<Vec<u8>, Error> {
+    if rounds == 0 {
+        return Err(Error::InvalidRounds { value: rounds });
+    }
+    let blocks = length.div_ceil(hash::DIGEST_LENGTH_BYTES);
+    if u32::try_from(blocks).is_err() {
+        return Err(Error::InvalidLength { value: length });
+    }
+
+    let salt = salt.as_ref();
+    let hmac = HmacSha224::new(password);
+    let mut key = Vec::with_capacity(length);
+    for index in 1..=u32::MAX {
+        if key.len() >= length {
+            break;
+        }
+        let mut first = hmac.clone();
+        first.update(salt);
+        first.update(index.to_be_bytes());
+        let mut digest = first.finalize();
+        let mut block = digest.into_inner();
+        for _ in 1..rounds {
+            let mut next = hmac.clone();
+            next.update(digest);
+            digest = next.finalize();
+            block
+                .iter_mut()
+                .zip(digest.as_bytes())
+                .for_each(|(block, byte)| *block ^= byte);
+        }
+        let remaining = length - key.len();
+        key.extend_from_slice(&block[..remaining.min(block.len())]);
+    }
+    Ok(key)
+}
+
+/// A password hash in the PHC-style string format.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PasswordHash {
+    /// The number of rounds.
+    pub rounds: u32,
+    /// The salt.
+    pub salt: Vec<u8>,
+    /// The derived key.
+    pub hash: Vec<u8>,
+}
+
+impl PasswordHash {
+    /// Hashes the password with the given salt and number of rounds.
+    ///
+    /// The derived key is as long as the SHA-2 224 digest. The salt should be unique per password and at least 16
+    /// bytes long.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRounds`] when the number of rounds is zero.
+    pub fn new(password: impl AsRef<[u8]>, salt: impl AsRef<[u8]>, rounds: u32) -> Result<Self, Error> {
+        let salt = salt.as_ref().to_vec();
+        let hash = derive(password, &salt, rounds, hash::DIGEST_LENGTH_BYTES)?;
+        Ok(Self { rounds, salt, hash })
+    }
+
+    /// Derives the key from the password and compares it with the stored one in constant time.
+    #[must_use]
+    pub fn verify(&self, password: impl AsRef<[u8]>) -> bool {
+        derive(password, &self.salt, self.rounds, self.hash.len()).is_ok_and(|hash| constant_time_eq(&hash, &self.hash))
+    }
+}
+
+impl Display for PasswordHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self { rounds, salt, hash } = self;
+        let salt = AB64.encode_bytes(salt);
+        let hash = AB64.encode_bytes(hash);
+        write!(f, "${IDENTIFIER}${rounds}${salt}${hash}")
+    }
+}
+
+impl FromStr for PasswordHash {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut fields = string.strip_prefix('$').ok_or(Error::InvalidFormat)?.split('$');
+        let (Some(algorithm), Some(rounds), Some(salt), Some(hash), None) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) else {
+            return Err(Error::InvalidFormat);
+        };
+        if algorithm != IDENTIFIER {
+            let algorithm = algorithm.to_owned();
+            return Err(Error::UnsupportedAlgorithm { algorithm });
+        }
+        // only the canonical decimal representation is accepted
+        if rounds.is_empty() || rounds.starts_with('0') || !rounds.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(Error::InvalidFormat);
+        }
+        let rounds = rounds.parse().map_err(|_| Error::InvalidFormat)?;
+        let salt = AB64.decode_bytes(salt, 0).map_err(Error::InvalidSalt)?;
+        let hash = AB64.decode_bytes(hash, 0).map_err(Error::InvalidHash)?;
+        if hash.is_empty() {
+            return Err(Error::InvalidFormat);
+        }
+        Ok(Self { rounds, salt, hash })
+    }
+}
+
+/// Parses the PHC-style password hash and verifies the password against it.
+///
+/// # Errors
+///
+/// Returns [`Error`] when the password hash cannot be parsed.
+pub fn verify(password: impl AsRef<[u8]>, hash: &str) -> Result<bool, Error> {
+    let hash = hash.parse::<PasswordHash>()?;
+    Ok(hash.verify(password))
+}
+
+/// An error type for the key derivation and password hashing.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// Represents a number of rounds equal to zero.
+    #[error("Invalid number of rounds `{value}`")]
+    InvalidRounds { value: u32 },
+    /// Represents a requested key length above `(2^32 - 1) * 28` bytes.
+    #[error("Invalid output length `{value}`")]
+    InvalidLength { value: usize },
+    /// Represents a string which doesn't match the `$pbkdf2-sha224$<rounds>$<salt>$<hash>` format.
+    #[error("Improperly formatted password hash")]
+    InvalidFormat,
+    /// Represents a password hash of an algorithm other than PBKDF2-SHA-2 224.
+    #[error("Unsupported algorithm `{algorithm}`")]
+    UnsupportedAlgorithm { algorithm: String },
+    /// Represents an improperly encoded salt.
+    #[error("Invalid salt")]
+    InvalidSalt(#[source] FormatError),
+    /// Represents an improperly encoded hash.
+    #[error("Invalid hash")]
+    InvalidHash(#[source] FormatError),
+}
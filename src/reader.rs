@@ -2,6 +2,13 @@
 //!
 //! The [`Reader`] allows on-the-fly calculation of the digest while reading the data.
 //!
+//! The [`VerifyingReader`] created by [`verifying`] also compares the digest with the expected one when the end of the
+//! data is reached, and fails with an [`io::Error`] of [`ErrorKind::InvalidData`] kind which wraps
+//! [`VerifyError::Mismatch`](crate::VerifyError::Mismatch) when they are different. The mismatch can be retrieved
+//! with [`VerifyError::from_io_error`](crate::VerifyError::from_io_error).
+//!
+//! The [`tee`] function reads the data once, copies it to any number of sinks and returns its digest.
+//!
 //! # Enabling
 //!
 //! Add the following entry to your `Cargo.toml` file to enable the `reader` feature:
@@ -44,13 +51,17 @@
 //! # }
 //! ```
 
-use std::io::Read;
+use std::io::{self, ErrorKind, Read, Write};
+#[cfg(feature = "async-runtime-tokio")]
+use std::pin::Pin;
+#[cfg(feature = "async-runtime-tokio")]
+use std::task::{ready, Context, Poll};
 
 use chksum_reader as reader;
 #[cfg(feature = "async-runtime-tokio")]
-use tokio::io::AsyncRead;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
 
-use crate::SHA2_224;
+use crate::{verify_io, ChksumOptions, Digest, SHA2_224};
 
 /// A specialized [`Reader`](reader::Reader) type with the [`SHA2_224`] hash algorithm.
 pub type Reader<R> = reader::Reader<R, SHA2_224>;
@@ -80,3 +91,224 @@ pub fn async_new(inner: impl AsyncRead) -> AsyncReader<impl AsyncRead> {
 pub fn async_with_hash(inner: impl AsyncRead, hash: SHA2_224) -> AsyncReader<impl AsyncRead> {
     reader::async_with_hash(inner, hash)
 }
+
+/// Creates new [`VerifyingReader`] which checks the digest of the data against the expected one.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io;
+///
+/// use chksum_sha2_224 as sha2_224;
+/// use sha2_224::VerifyError;
+///
+/// let expected = sha2_224::hash(b"example data");
+/// let mut reader = sha2_224::reader::verifying(&b"example data"[..], expected);
+/// let mut output = Vec::new();
+/// io::copy(&mut reader, &mut output).unwrap();
+///
+/// let mut reader = sha2_224::reader::verifying(&b"corrupted data"[..], expected);
+/// let error = io::copy(&mut reader, &mut io::sink()).unwrap_err();
+/// assert!(matches!(
+///     VerifyError::from_io_error(&error),
+///     Some(VerifyError::Mismatch { .. })
+/// ));
+/// ```
+pub fn verifying<R: Read>(inner: R, expected: Digest) -> VerifyingReader<R> {
+    VerifyingReader::new(inner, expected)
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Creates new [`AsyncVerifyingReader`] which checks the digest of the data against the expected one.
+pub fn async_verifying<R: AsyncRead + Unpin>(inner: R, expected: Digest) -> AsyncVerifyingReader<R> {
+    AsyncVerifyingReader::new(inner, expected)
+}
+
+/// A reader which verifies the digest of the data at the end of the data.
+///
+/// Every read at the end of the data fails until the digest matches, so the mismatch cannot be missed.
+#[derive(Debug)]
+pub struct VerifyingReader<R> {
+    inner: R,
+    hash: SHA2_224,
+    expected: Digest,
+}
+
+impl<R: Read> VerifyingReader<R> {
+    /// Creates new reader.
+    pub fn new(inner: R, expected: Digest) -> Self {
+        let hash = SHA2_224::new();
+        Self { inner, hash, expected }
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for VerifyingReader<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let length = self.inner.read(buffer)?;
+        if length > 0 {
+            self.hash.update(&buffer[..length]);
+        } else if !buffer.is_empty() {
+            verify_io(&self.hash, &self.expected)?;
+        }
+        Ok(length)
+    }
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// An asynchronous reader which verifies the digest of the data at the end of the data.
+#[derive(Debug)]
+pub struct AsyncVerifyingReader<R> {
+    inner: R,
+    hash: SHA2_224,
+    expected: Digest,
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+impl<R: AsyncRead + Unpin> AsyncVerifyingReader<R> {
+    /// Creates new reader.
+    pub fn new(inner: R, expected: Digest) -> Self {
+        let hash = SHA2_224::new();
+        Self { inner, hash, expected }
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncVerifyingReader<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buffer: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let Self { inner, hash, expected } = self.get_mut();
+        let filled = buffer.filled().len();
+        ready!(Pin::new(inner).poll_read(cx, buffer))?;
+        let data = &buffer.filled()[filled..];
+        if !data.is_empty() {
+            hash.update(data);
+        } else if buffer.remaining() > 0 {
+            verify_io(hash, expected)?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Reads the data, writes it to all sinks and computes its digest.
+///
+/// Every chunk of the data is written to the sinks in their order, and all sinks are flushed at the end.
+///
+/// # Errors
+///
+/// Returns [`TeeError::Read`] when reading fails and [`TeeError::Write`] when writing to or flushing a sink fails.
+/// Copying stops at the first error, so sinks may end up with different amounts of data: every sink has received the
+/// number of bytes reported by [`TeeError::Write`], and sinks before the failing one may have received one more
+/// chunk.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// # fn wrapper() -> Result<(), sha2_224::reader::TeeError> {
+/// let mut file = Vec::new();
+/// let mut backup = Vec::new();
+/// let output = sha2_224::reader::tee(&b"example data"[..], &mut [&mut file, &mut backup])?;
+/// assert_eq!(output.digest, sha2_224::hash(b"example data"));
+/// assert_eq!(output.length, 12);
+/// assert_eq!(file, b"example data");
+/// assert_eq!(backup, b"example data");
+/// # Ok(())
+/// # }
+/// ```
+pub fn tee(inner: impl Read, sinks: &mut [&mut dyn Write]) -> Result<TeeOutput, TeeError> {
+    let mut reader = Reader::new(inner);
+    let mut buffer = vec![0; ChksumOptions::DEFAULT_BUFFER_SIZE];
+    let mut length = 0;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(TeeError::Read(error)),
+        };
+        for (index, sink) in sinks.iter_mut().enumerate() {
+            sink.write_all(&buffer[..read])
+                .map_err(|source| TeeError::Write { index, length, source })?;
+        }
+        length += read as u64;
+    }
+    for (index, sink) in sinks.iter_mut().enumerate() {
+        sink.flush()
+            .map_err(|source| TeeError::Write { index, length, source })?;
+    }
+    let digest = reader.digest();
+    Ok(TeeOutput { digest, length })
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Reads the data asynchronously, writes it to all sinks and computes its digest.
+///
+/// Sinks are written one after another, with the same error semantics as [`tee`].
+///
+/// # Errors
+///
+/// Returns [`TeeError::Read`] when reading fails and [`TeeError::Write`] when writing to or flushing a sink fails.
+pub async fn async_tee(
+    inner: impl AsyncRead + Unpin,
+    sinks: &mut [&mut (dyn AsyncWrite + Unpin + Send)],
+) -> Result<TeeOutput, TeeError> {
+    let mut reader = AsyncReader::new(inner);
+    let mut buffer = vec![0; ChksumOptions::DEFAULT_BUFFER_SIZE];
+    let mut length = 0;
+    loop {
+        let read = match reader.read(&mut buffer).await {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(TeeError::Read(error)),
+        };
+        for (index, sink) in sinks.iter_mut().enumerate() {
+            sink.write_all(&buffer[..read])
+                .await
+                .map_err(|source| TeeError::Write { index, length, source })?;
+        }
+        length += read as u64;
+    }
+    for (index, sink) in sinks.iter_mut().enumerate() {
+        sink.flush()
+            .await
+            .map_err(|source| TeeError::Write { index, length, source })?;
+    }
+    let digest = reader.digest();
+    Ok(TeeOutput { digest, length })
+}
+
+/// A result of the [`tee`] function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TeeOutput {
+    /// The digest of the data.
+    pub digest: Digest,
+    /// The number of bytes written to every sink.
+    pub length: u64,
+}
+
+/// An error type for the [`tee`] function.
+#[derive(Debug, thiserror::Error)]
+pub enum TeeError {
+    /// Represents a failure of the reader.
+    #[error("Cannot read data")]
+    Read(#[source] io::Error),
+    /// Represents a failure of the sink at the given index, after the given number of bytes was written to all sinks.
+    #[error("Cannot write data to sink `{index}` after `{length}` bytes")]
+    Write {
+        index: usize,
+        length: u64,
+        #[source]
+        source: io::Error,
+    },
+}
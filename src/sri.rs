@@ -0,0 +1,209 @@
+//! This module provides [Subresource Integrity](https://www.w3.org/TR/SRI/) metadata generation and verification.
+//!
+//! Integrity metadata has the form of `sha224-<base64>`, where the digest is encoded in the standard base64
+//! representation with padding. Metadata strings may contain several whitespace-separated entries, possibly for
+//! different hash algorithms and with options appended after the `?` character. Only `sha224` entries are taken into
+//! account, entries for other algorithms are ignored.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! use chksum_sha2_224 as sha2_224;
+//!
+//! # fn wrapper(path: &Path) -> Result<(), sha2_224::sri::Error> {
+//! let file = File::open(path)?;
+//! let integrity = sha2_224::sri::chksum(file)?;
+//! assert_eq!(integrity, "sha224-kDgsv9omVjE61h/XSzLd+kvMEY9mC9T7qSKM7Q==");
+//!
+//! let file = File::open(path)?;
+//! let integrity = "sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU= \
+//!                  sha224-kDgsv9omVjE61h/XSzLd+kvMEY9mC9T7qSKM7Q==";
+//! assert!(sha2_224::sri::verify(file, integrity)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{core, Digest, FormatError};
+
+/// The hash algorithm token of SHA-2 224 integrity metadata.
+pub const ALGORITHM: &str = "sha224";
+
+/// Returns the integrity metadata of the digest.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// let digest = sha2_224::hash(b"");
+/// assert_eq!(
+///     sha2_224::sri::encode(&digest),
+///     "sha224-0UoCjCo6K8lHYQK7KII0xBWisB+CjqYqxbPkLw=="
+/// );
+/// ```
+#[must_use]
+pub fn encode(digest: &Digest) -> String {
+    format!("{ALGORITHM}-{}", digest.to_base64())
+}
+
+/// Computes the hash of the given input and returns its integrity metadata.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// let integrity = sha2_224::sri::hash(b"");
+/// assert_eq!(integrity, "sha224-0UoCjCo6K8lHYQK7KII0xBWisB+CjqYqxbPkLw==");
+/// ```
+#[must_use]
+pub fn hash(data: impl core::Hashable) -> String {
+    encode(&crate::hash(data))
+}
+
+/// Computes the hash of the given input and returns its integrity metadata.
+///
+/// # Errors
+///
+/// Returns [`crate::Error`] when the hash cannot be computed.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// # fn wrapper() -> sha2_224::Result<()> {
+/// let integrity = sha2_224::sri::chksum(b"")?;
+/// assert_eq!(integrity, "sha224-0UoCjCo6K8lHYQK7KII0xBWisB+CjqYqxbPkLw==");
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum(data: impl core::Chksumable) -> crate::Result<String> {
+    let digest = crate::chksum(data)?;
+    Ok(encode(&digest))
+}
+
+/// Computes the hash of the given input and returns its integrity metadata.
+///
+/// # Errors
+///
+/// Returns [`crate::Error`] when the hash cannot be computed.
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(data: impl core::AsyncChksumable) -> crate::Result<String> {
+    let digest = crate::async_chksum(data).await?;
+    Ok(encode(&digest))
+}
+
+/// Parses integrity metadata and returns the digests of all `sha224` entries.
+///
+/// # Errors
+///
+/// Returns [`Error`] when an entry is malformed, when a `sha224` entry doesn't contain a valid digest or when there is
+/// no `sha224` entry at all.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// let digests =
+///     sha2_224::sri::parse("sha224-0UoCjCo6K8lHYQK7KII0xBWisB+CjqYqxbPkLw==?ct=text/plain")
+///         .unwrap();
+/// assert_eq!(digests, [sha2_224::hash(b"")]);
+/// ```
+pub fn parse(integrity: &str) -> Result<Vec<Digest>, Error> {
+    let mut digests = Vec::new();
+    for entry in integrity.split_ascii_whitespace() {
+        let (algorithm, value) = entry.split_once('-').ok_or_else(|| {
+            Error::InvalidEntry {
+                entry: entry.to_owned(),
+            }
+        })?;
+        if algorithm.is_empty() {
+            let error = Error::InvalidEntry {
+                entry: entry.to_owned(),
+            };
+            return Err(error);
+        }
+        if !algorithm.eq_ignore_ascii_case(ALGORITHM) {
+            continue;
+        }
+        // options are reserved for future use and must be ignored
+        let value = value.split_once('?').map_or(value, |(value, _)| value);
+        let digest = Digest::try_from_base64(value).map_err(|source| {
+            Error::InvalidDigest {
+                entry: entry.to_owned(),
+                source,
+            }
+        })?;
+        digests.push(digest);
+    }
+    if digests.is_empty() {
+        return Err(Error::MissingDigest);
+    }
+    Ok(digests)
+}
+
+/// Computes the hash of the given input and checks whether it matches any `sha224` entry of the integrity metadata.
+///
+/// Digests are compared in constant time.
+///
+/// # Errors
+///
+/// Returns [`Error`] when the integrity metadata is malformed or when the hash cannot be computed.
+pub fn verify(data: impl core::Chksumable, integrity: &str) -> Result<bool, Error> {
+    let expected = parse(integrity)?;
+    let digest = crate::chksum(data)?;
+    Ok(matches(&digest, &expected))
+}
+
+/// Computes the hash of the given input and checks whether it matches any `sha224` entry of the integrity metadata.
+///
+/// Digests are compared in constant time.
+///
+/// # Errors
+///
+/// Returns [`Error`] when the integrity metadata is malformed or when the hash cannot be computed.
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_verify(data: impl core::AsyncChksumable, integrity: &str) -> Result<bool, Error> {
+    let expected = parse(integrity)?;
+    let digest = crate::async_chksum(data).await?;
+    Ok(matches(&digest, &expected))
+}
+
+fn matches(digest: &Digest, expected: &[Digest]) -> bool {
+    // every entry is compared so the time doesn't depend on the position of the matching one
+    expected
+        .iter()
+        .fold(false, |matches, expected| digest.ct_eq(expected) | matches)
+}
+
+/// An error type for the integrity metadata operations.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Represents an entry without the hash algorithm token.
+    #[error("Invalid integrity entry `{entry}`")]
+    InvalidEntry { entry: String },
+    /// Represents a `sha224` entry with an invalid digest.
+    #[error("Invalid digest in integrity entry `{entry}`")]
+    InvalidDigest {
+        entry: String,
+        #[source]
+        source: FormatError,
+    },
+    /// Represents integrity metadata without any `sha224` entry.
+    #[error("Missing `sha224` integrity entry")]
+    MissingDigest,
+    /// The digest cannot be calculated.
+    #[error(transparent)]
+    Chksum(#[from] crate::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::Chksum(error.into())
+    }
+}
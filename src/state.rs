@@ -0,0 +1,339 @@
+//! This module contains the [`State`] structure, an exportable snapshot of an in-progress hash computation.
+//!
+//! A state can be encoded into bytes, stored, and later imported to continue hashing exactly where it was
+//! interrupted, e.g. after a restart of the process. Only hashes created by
+//! [`SHA2_224::resumable`](crate::SHA2_224::resumable) and [`SHA2_224::from_state`](crate::SHA2_224::from_state) can
+//! export their state, because the upstream implementation used by [`SHA2_224::new`](crate::SHA2_224::new) keeps it
+//! private.
+//!
+//! # Encoding
+//!
+//! The byte encoding is stable. Its first version consists of the following fields:
+//!
+//! | Offset | Length | Description                                                  |
+//! |--------|--------|--------------------------------------------------------------|
+//! | 0      | 6      | the `SHA224` signature in ASCII                              |
+//! | 6      | 1      | the version of the encoding, equal to `1`                    |
+//! | 7      | 32     | eight chaining values, each as a big-endian 32-bit integer   |
+//! | 39     | 8      | the number of processed bytes as a big-endian 64-bit integer |
+//! | 47     | 1      | the number of buffered bytes, lower than 64                  |
+//! | 48     | 0..64  | the buffered bytes                                           |
+//!
+//! The number of processed bytes covers only complete blocks, so it is always a multiple of 64.
+//!
+//! With the `serde` feature enabled, [`State`] is serialized using the same encoding, as a base64 string in
+//! human-readable formats and as raw bytes otherwise.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha2_224 as sha2_224;
+//! use sha2_224::state::State;
+//! use sha2_224::SHA2_224;
+//!
+//! # fn wrapper() -> Result<(), sha2_224::state::Error> {
+//! let mut hash = SHA2_224::resumable();
+//! hash.update(b"example ");
+//! let checkpoint = hash.export_state().unwrap().to_bytes();
+//!
+//! // ...
+//!
+//! let state = State::from_bytes(&checkpoint)?;
+//! let mut hash = SHA2_224::from_state(state);
+//! hash.update(b"data");
+//! assert_eq!(hash.digest(), sha2_224::hash(b"example data"));
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "serde")]
+use std::fmt::{self, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, Serializer};
+
+use crate::compression::{compress, H};
+#[cfg(feature = "serde")]
+use crate::encoding::BASE64;
+use crate::{hash, Digest};
+
+/// The signature at the beginning of the encoded state.
+pub const SIGNATURE: &[u8; 6] = b"SHA224";
+
+/// The current version of the encoding.
+pub const VERSION: u8 = 1;
+
+const BLOCK_LENGTH_BYTES: usize = hash::BLOCK_LENGTH_BYTES;
+const HEADER_LENGTH_BYTES: usize = SIGNATURE.len() + 1 + 8 * 4 + 8 + 1;
+
+/// A snapshot of an in-progress hash computation.
+///
+/// Check [`state`](self) module for usage examples.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct State {
+    chaining: [u32; 8],
+    unprocessed: Vec<u8>,
+    processed: u64,
+}
+
+impl State {
+    /// Creates a new state.
+    pub(crate) fn new() -> Self {
+        Self {
+            chaining: H,
+            unprocessed: Vec::with_capacity(BLOCK_LENGTH_BYTES),
+            processed: 0,
+        }
+    }
+
+    /// Returns the total number of bytes hashed so far.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224::SHA2_224;
+    ///
+    /// let mut hash = SHA2_224::resumable();
+    /// hash.update(b"example data");
+    /// assert_eq!(hash.export_state().unwrap().length(), 12);
+    /// ```
+    #[must_use]
+    pub fn length(&self) -> u64 {
+        // the buffer is always shorter than a block
+        self.processed.wrapping_add(self.unprocessed.len() as u64)
+    }
+
+    /// Encodes the state into bytes.
+    ///
+    /// Check [`state`](self) module for the description of the encoding.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH_BYTES + self.unprocessed.len());
+        bytes.extend_from_slice(SIGNATURE);
+        bytes.push(VERSION);
+        for value in self.chaining {
+            bytes.extend_from_slice(&value.to_be_bytes());
+        }
+        bytes.extend_from_slice(&self.processed.to_be_bytes());
+        // the buffer is always shorter than a block
+        bytes.push(self.unprocessed.len() as u8);
+        bytes.extend_from_slice(&self.unprocessed);
+        bytes
+    }
+
+    /// Decodes the state from bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when the bytes are not a valid encoding of the state.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let Some(bytes) = bytes.strip_prefix(SIGNATURE) else {
+            return Err(Error::InvalidSignature);
+        };
+        let Some((&version, bytes)) = bytes.split_first() else {
+            return Err(Error::InvalidSignature);
+        };
+        if version != VERSION {
+            return Err(Error::UnsupportedVersion { version });
+        }
+        let header = HEADER_LENGTH_BYTES - SIGNATURE.len() - 1;
+        if bytes.len() < header {
+            let error = Error::InvalidLength {
+                value: bytes.len() + SIGNATURE.len() + 1,
+                proper: HEADER_LENGTH_BYTES,
+            };
+            return Err(error);
+        }
+
+        let (chaining, bytes) = bytes.split_at(8 * 4);
+        let mut values = [0u32; 8];
+        for (value, bytes) in values.iter_mut().zip(chaining.chunks_exact(4)) {
+            *value = u32::from_be_bytes(bytes.try_into().expect("chunk must have exact length"));
+        }
+        let (processed, bytes) = bytes.split_at(8);
+        let processed = u64::from_be_bytes(processed.try_into().expect("slice must have exact length"));
+        let (&buffered, unprocessed) = bytes.split_first().expect("header must contain buffer length");
+
+        let buffered = usize::from(buffered);
+        if buffered >= BLOCK_LENGTH_BYTES {
+            return Err(Error::InvalidBufferLength { value: buffered });
+        }
+        if unprocessed.len() != buffered {
+            let error = Error::InvalidLength {
+                value: HEADER_LENGTH_BYTES + unprocessed.len(),
+                proper: HEADER_LENGTH_BYTES + buffered,
+            };
+            return Err(error);
+        }
+        if processed % BLOCK_LENGTH_BYTES as u64 != 0 {
+            return Err(Error::InvalidProcessedLength { value: processed });
+        }
+
+        let mut state = Self::new();
+        state.chaining = values;
+        state.unprocessed.extend_from_slice(unprocessed);
+        state.processed = processed;
+        Ok(state)
+    }
+
+    /// Updates the state with an input data.
+    pub(crate) fn update(&mut self, mut data: &[u8]) {
+        if !self.unprocessed.is_empty() {
+            let missing = BLOCK_LENGTH_BYTES - self.unprocessed.len();
+            let (fillment, rest) = data.split_at(missing.min(data.len()));
+            self.unprocessed.extend_from_slice(fillment);
+            data = rest;
+            if self.unprocessed.len() < BLOCK_LENGTH_BYTES {
+                return;
+            }
+            let block = self
+                .unprocessed
+                .as_slice()
+                .try_into()
+                .expect("buffer must contain a whole block");
+            self.chaining = compress(self.chaining, block);
+            self.processed = self.processed.wrapping_add(BLOCK_LENGTH_BYTES as u64);
+            self.unprocessed.clear();
+        }
+
+        let mut chunks = data.chunks_exact(BLOCK_LENGTH_BYTES);
+        for chunk in chunks.by_ref() {
+            let block = chunk.try_into().expect("chunk must contain a whole block");
+            self.chaining = compress(self.chaining, block);
+            self.processed = self.processed.wrapping_add(BLOCK_LENGTH_BYTES as u64);
+        }
+        self.unprocessed.extend_from_slice(chunks.remainder());
+    }
+
+    /// Applies padding and produces the digest without modifying the state.
+    pub(crate) fn digest(&self) -> Digest {
+        let length = self.length().wrapping_mul(8).to_be_bytes();
+        let mut padding = [0u8; BLOCK_LENGTH_BYTES * 2];
+        padding[..self.unprocessed.len()].copy_from_slice(&self.unprocessed);
+        padding[self.unprocessed.len()] = 0x80;
+        let blocks = if self.unprocessed.len() + 1 + length.len() <= BLOCK_LENGTH_BYTES {
+            1
+        } else {
+            2
+        };
+        let end = blocks * BLOCK_LENGTH_BYTES;
+        padding[end - length.len()..end].copy_from_slice(&length);
+
+        let mut chaining = self.chaining;
+        for block in padding[..end].chunks_exact(BLOCK_LENGTH_BYTES) {
+            chaining = compress(chaining, block.try_into().expect("chunk must contain a whole block"));
+        }
+
+        let mut digest = [0u8; hash::DIGEST_LENGTH_BYTES];
+        for (bytes, value) in digest.chunks_exact_mut(4).zip(chaining) {
+            bytes.copy_from_slice(&value.to_be_bytes());
+        }
+        Digest::new(digest)
+    }
+
+    /// Resets the state to its initial values.
+    pub(crate) fn reset(&mut self) {
+        self.chaining = H;
+        self.unprocessed.clear();
+        self.processed = 0;
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serializes the encoded state as a base64 string in human-readable formats and as raw bytes otherwise.
+#[cfg(feature = "serde")]
+impl Serialize for State {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bytes = self.to_bytes();
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&BASE64.encode_bytes(&bytes))
+        } else {
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+/// Deserializes the encoded state from a base64 string in human-readable formats and from raw bytes otherwise.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for State {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StateVisitor;
+
+        impl<'de> Visitor<'de> for StateVisitor {
+            type Value = State;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("a base64 string or bytes of an encoded state")
+            }
+
+            fn visit_str<E>(self, state: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let bytes = BASE64.decode_bytes(state, 0).map_err(E::custom)?;
+                self.visit_bytes(&bytes)
+            }
+
+            fn visit_bytes<E>(self, state: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                State::from_bytes(state).map_err(E::custom)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut bytes = Vec::with_capacity(
+                    seq.size_hint()
+                        .unwrap_or(0)
+                        .min(HEADER_LENGTH_BYTES + BLOCK_LENGTH_BYTES),
+                );
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                self.visit_bytes(&bytes)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(StateVisitor)
+        } else {
+            deserializer.deserialize_bytes(StateVisitor)
+        }
+    }
+}
+
+/// An error type for the state decoding.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// Represents bytes which don't start with the [`SIGNATURE`] and the version.
+    #[error("Invalid state signature")]
+    InvalidSignature,
+    /// Represents an encoding version other than [`VERSION`].
+    #[error("Unsupported state version `{version}`")]
+    UnsupportedVersion { version: u8 },
+    /// Represents bytes of an unexpected length.
+    #[error("Invalid length `{value}`, proper value `{proper}`")]
+    InvalidLength { value: usize, proper: usize },
+    /// Represents a number of buffered bytes which is not lower than the block length.
+    #[error("Invalid buffer length `{value}`, maximum value `63`")]
+    InvalidBufferLength { value: usize },
+    /// Represents a number of processed bytes which is not a multiple of the block length.
+    #[error("Invalid processed length `{value}`, must be a multiple of `64`")]
+    InvalidProcessedLength { value: u64 },
+}
@@ -0,0 +1,624 @@
+//! This module provides a tree hashing mode, which splits the input into fixed-size leaves, hashes them in parallel
+//! and combines the results into a single root digest.
+//!
+//! # Construction
+//!
+//! * every leaf is hashed as `SHA-224(0x00 || leaf)`,
+//! * every pair of adjacent nodes is combined as `SHA-224(0x01 || left || right)`,
+//! * the last node of a level with an odd number of nodes is promoted to the next level unchanged,
+//! * an empty input consists of a single empty leaf,
+//! * the single node of the last level is finalized as `SHA-224(0x02 || leaves || leaf_size || top)`, where the
+//!   number of leaves and the size of a leaf are 64-bit big-endian integers.
+//!
+//! The prefixes separate leaves from inner nodes, so the root is never equal to the plain SHA-2 224 digest of the
+//! same input. The root depends on the leaf size but not on the number of threads.
+//!
+//! Since the promotion of odd nodes makes the shape of the tree ambiguous, the finalization binds the number of leaves
+//! and the size of a leaf into the root. Without it, a proof claiming a different number of leaves could place a leaf
+//! at a different position of the input.
+//!
+//! A [`Proof`] generated for a single leaf allows to verify that the leaf belongs to the tree with a known root
+//! without access to the rest of the input.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha2_224 as sha2_224;
+//! use sha2_224::tree::TreeHasher;
+//!
+//! let data = vec![0u8; 10_000];
+//! let tree = TreeHasher::new().leaf_size(1024).threads(4).hash(&data);
+//! assert_eq!(tree.leaves().len(), 10);
+//! assert_eq!(
+//!     tree.root(),
+//!     TreeHasher::new()
+//!         .leaf_size(1024)
+//!         .threads(1)
+//!         .hash(&data)
+//!         .root()
+//! );
+//! assert_ne!(tree.root(), sha2_224::hash(&data));
+//! ```
+
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{mpsc, Mutex};
+use std::{result, thread};
+
+use crate::hash::DIGEST_LENGTH_BYTES;
+use crate::{Digest, Result, SHA2_224};
+
+/// The default size of a leaf, equal to 1 MiB.
+pub const DEFAULT_LEAF_SIZE: usize = 1024 * 1024;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+const ROOT_PREFIX: u8 = 0x02;
+
+/// Computes the hash of a single leaf.
+#[must_use]
+pub fn hash_leaf(data: impl AsRef<[u8]>) -> Digest {
+    let mut hash = SHA2_224::new();
+    hash.update([LEAF_PREFIX]);
+    hash.update(data);
+    hash.digest()
+}
+
+/// Computes the hash of an inner node from the hashes of its children.
+#[must_use]
+pub fn hash_node(left: &Digest, right: &Digest) -> Digest {
+    let mut hash = SHA2_224::new();
+    hash.update([NODE_PREFIX]);
+    hash.update(left);
+    hash.update(right);
+    hash.digest()
+}
+
+/// Computes the root digest from the top node of the tree.
+#[must_use]
+pub fn hash_root(leaves: usize, leaf_size: usize, top: &Digest) -> Digest {
+    let mut hash = SHA2_224::new();
+    hash.update([ROOT_PREFIX]);
+    hash.update((leaves as u64).to_be_bytes());
+    hash.update((leaf_size as u64).to_be_bytes());
+    hash.update(top);
+    hash.digest()
+}
+
+/// Computes the tree of the given input with the default options.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_224 as sha2_224;
+///
+/// let tree = sha2_224::tree::hash(b"data");
+/// assert_eq!(
+///     tree.root().to_hex_lowercase(),
+///     "bffdd8ad6acfab2db95d4f1882e4dd01cd7636bd3d8f98ba4b188ebc"
+/// );
+/// ```
+#[must_use]
+pub fn hash(data: impl AsRef<[u8]>) -> Tree {
+    TreeHasher::new().hash(data)
+}
+
+/// Computes the tree of the file with the default options.
+///
+/// # Errors
+///
+/// Returns [`Error`](crate::Error) when the file cannot be opened or read.
+pub fn chksum(path: impl AsRef<Path>) -> Result<Tree> {
+    TreeHasher::new().chksum(path)
+}
+
+/// A configurable tree hasher.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeHasher {
+    leaf_size: usize,
+    threads: usize,
+    #[cfg(feature = "mmap")]
+    mmap_threshold: Option<u64>,
+}
+
+impl TreeHasher {
+    /// Creates a new hasher with leaves of [`DEFAULT_LEAF_SIZE`] bytes and one thread per available CPU.
+    #[must_use]
+    pub fn new() -> Self {
+        let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        Self {
+            leaf_size: DEFAULT_LEAF_SIZE,
+            threads,
+            #[cfg(feature = "mmap")]
+            mmap_threshold: None,
+        }
+    }
+
+    /// Sets the size of a leaf in bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the size is zero.
+    #[must_use]
+    pub fn leaf_size(mut self, leaf_size: usize) -> Self {
+        assert!(leaf_size > 0, "leaf size must be greater than zero");
+        self.leaf_size = leaf_size;
+        self
+    }
+
+    /// Sets the number of threads used to hash leaves.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the number is zero.
+    #[must_use]
+    pub fn threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "number of threads must be greater than zero");
+        self.threads = threads;
+        self
+    }
+
+    /// Sets the minimal length of files which are mapped into memory by [`TreeHasher::chksum`], `None`, the
+    /// default, disables mapping.
+    ///
+    /// Check the [safety notes](crate::mmap#safety) before enabling it.
+    #[cfg(feature = "mmap")]
+    #[must_use]
+    pub fn mmap_threshold(mut self, threshold: Option<u64>) -> Self {
+        self.mmap_threshold = threshold;
+        self
+    }
+
+    /// Computes the tree of the given input.
+    #[must_use]
+    pub fn hash(&self, data: impl AsRef<[u8]>) -> Tree {
+        let data = data.as_ref();
+        let chunks: Vec<&[u8]> = if data.is_empty() {
+            vec![data]
+        } else {
+            data.chunks(self.leaf_size).collect()
+        };
+        let group = chunks.len().div_ceil(self.threads);
+        let leaves = if group == chunks.len() {
+            chunks.iter().map(hash_leaf).collect()
+        } else {
+            thread::scope(|scope| {
+                let handles: Vec<_> = chunks
+                    .chunks(group)
+                    .map(|chunks| scope.spawn(move || chunks.iter().map(hash_leaf).collect::<Vec<_>>()))
+                    .collect();
+                handles
+                    .into_iter()
+                    .flat_map(|handle| handle.join().expect("worker thread must not panic"))
+                    .collect()
+            })
+        };
+        Tree::from_leaves(self.leaf_size, leaves)
+    }
+
+    /// Computes the tree of the data read from the reader.
+    ///
+    /// The data is read sequentially, and the leaves are hashed by the worker threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`](crate::Error) when the reader fails.
+    pub fn read(&self, mut reader: impl Read) -> Result<Tree> {
+        let leaf_size = self.leaf_size;
+        let (jobs, queue) = mpsc::sync_channel::<(usize, Vec<u8>)>(self.threads * 2);
+        let queue = Mutex::new(queue);
+        let (sender, results) = mpsc::channel();
+        let read: std::io::Result<usize> = thread::scope(|scope| {
+            for _ in 0..self.threads {
+                let queue = &queue;
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    loop {
+                        let job = queue.lock().expect("queue lock must not be poisoned").recv();
+                        let Ok((index, leaf)) = job else {
+                            break;
+                        };
+                        if sender.send((index, hash_leaf(leaf))).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut count = 0;
+            loop {
+                let leaf = read_leaf(&mut reader, leaf_size)?;
+                if leaf.is_empty() && count > 0 {
+                    break;
+                }
+                let last = leaf.len() < leaf_size;
+                jobs.send((count, leaf)).expect("worker threads must be running");
+                count += 1;
+                if last {
+                    break;
+                }
+            }
+            drop(jobs);
+            Ok(count)
+        });
+        let count = read?;
+
+        let mut leaves = vec![None; count];
+        for (index, digest) in results {
+            leaves[index] = Some(digest);
+        }
+        let leaves = leaves
+            .into_iter()
+            .map(|digest| digest.expect("every leaf must be hashed"))
+            .collect();
+        Ok(Tree::from_leaves(leaf_size, leaves))
+    }
+
+    /// Computes the tree of the file.
+    ///
+    /// Files are mapped into memory only when enabled with [`TreeHasher::mmap_threshold`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`](crate::Error) when the file cannot be opened or read.
+    pub fn chksum(&self, path: impl AsRef<Path>) -> Result<Tree> {
+        let file = File::open(path)?;
+        #[cfg(feature = "mmap")]
+        if let Some(map) = self
+            .mmap_threshold
+            .and_then(|threshold| crate::mmap::map(&file, threshold))
+        {
+            return Ok(self.hash(map));
+        }
+        self.read(file)
+    }
+}
+
+impl Default for TreeHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads a whole leaf, the leaf is shorter only at the end of the input.
+fn read_leaf(reader: &mut impl Read, leaf_size: usize) -> std::io::Result<Vec<u8>> {
+    let mut leaf = vec![0u8; leaf_size];
+    let mut filled = 0;
+    while filled < leaf_size {
+        match reader.read(&mut leaf[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => {},
+            Err(error) => return Err(error),
+        }
+    }
+    leaf.truncate(filled);
+    Ok(leaf)
+}
+
+/// A hash tree with all levels from the leaves up to the top node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tree {
+    leaf_size: usize,
+    levels: Vec<Vec<Digest>>,
+}
+
+impl Tree {
+    fn from_leaves(leaf_size: usize, leaves: Vec<Digest>) -> Self {
+        let mut levels = vec![leaves];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next = level
+                .chunks(2)
+                .map(|pair| {
+                    match pair {
+                        [left, right] => hash_node(left, right),
+                        [node] => *node,
+                        _ => unreachable!("chunks must contain one or two nodes"),
+                    }
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { leaf_size, levels }
+    }
+
+    /// Returns the root digest.
+    #[must_use]
+    pub fn root(&self) -> Digest {
+        let top = self
+            .levels
+            .last()
+            .and_then(|level| level.first())
+            .expect("tree must contain at least one node");
+        hash_root(self.leaves().len(), self.leaf_size, top)
+    }
+
+    /// Returns the size of a leaf used to build the tree.
+    #[must_use]
+    pub fn leaf_size(&self) -> usize {
+        self.leaf_size
+    }
+
+    /// Returns the hashes of leaves.
+    #[must_use]
+    pub fn leaves(&self) -> &[Digest] {
+        &self.levels[0]
+    }
+
+    /// Returns all levels, starting with the leaves and ending with the top node.
+    ///
+    /// The top node is not the root, check [`hash_root`] for the finalization.
+    #[must_use]
+    pub fn levels(&self) -> &[Vec<Digest>] {
+        &self.levels
+    }
+
+    /// Checks whether the data matches the leaf at the given index.
+    ///
+    /// Returns `false` when there is no such leaf.
+    #[must_use]
+    pub fn verify_leaf(&self, index: usize, data: impl AsRef<[u8]>) -> bool {
+        self.leaves().get(index).is_some_and(|leaf| hash_leaf(data).ct_eq(leaf))
+    }
+
+    /// Generates the inclusion proof of the leaf at the given index.
+    ///
+    /// Returns `None` when there is no such leaf.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_224::tree::TreeHasher;
+    ///
+    /// let data = b"example data";
+    /// let tree = TreeHasher::new().leaf_size(4).hash(data);
+    /// let proof = tree.proof(1).unwrap();
+    /// assert!(proof.verify(b"ple ", &tree.root()));
+    /// assert!(!proof.verify(b"exam", &tree.root()));
+    /// ```
+    #[must_use]
+    pub fn proof(&self, index: usize) -> Option<Proof> {
+        let leaves = self.leaves().len();
+        if index >= leaves {
+            return None;
+        }
+        let mut siblings = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(*sibling);
+            }
+            position /= 2;
+        }
+        Some(Proof {
+            index,
+            leaves,
+            leaf_size: self.leaf_size,
+            siblings,
+        })
+    }
+}
+
+/// An inclusion proof of a single leaf.
+///
+/// The leaf at index `i` covers the bytes from `i * leaf_size` up to, but excluding, `(i + 1) * leaf_size` of the
+/// input.
+///
+/// # Encoding
+///
+/// The binary encoding starts with the version byte equal to `1`, followed by the size of a leaf, the number of leaves
+/// and the index of the leaf, all as unsigned LEB128 integers, and by the sibling digests ordered from the leaf level
+/// up to the root. The number of siblings is implied by the number of leaves and the index.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Proof {
+    index: usize,
+    leaves: usize,
+    leaf_size: usize,
+    siblings: Vec<Digest>,
+}
+
+impl Proof {
+    /// The current version of the encoding.
+    pub const VERSION: u8 = 1;
+
+    /// Returns the index of the leaf.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the number of leaves in the tree.
+    #[must_use]
+    pub fn leaves(&self) -> usize {
+        self.leaves
+    }
+
+    /// Returns the size of a leaf used to build the tree.
+    #[must_use]
+    pub fn leaf_size(&self) -> usize {
+        self.leaf_size
+    }
+
+    /// Returns the range of bytes of the input covered by the leaf.
+    ///
+    /// The last leaf may be shorter than the returned range.
+    #[must_use]
+    pub fn range(&self) -> Range<u64> {
+        let start = self.index as u64 * self.leaf_size as u64;
+        start..start + self.leaf_size as u64
+    }
+
+    /// Returns the sibling digests ordered from the leaf level up to the root.
+    #[must_use]
+    pub fn siblings(&self) -> &[Digest] {
+        &self.siblings
+    }
+
+    /// Computes the root digest from the hash of the leaf.
+    #[must_use]
+    pub fn root(&self, leaf: &Digest) -> Digest {
+        let mut node = *leaf;
+        let mut position = self.index;
+        let mut nodes = self.leaves;
+        let mut siblings = self.siblings.iter();
+        while nodes > 1 {
+            if position % 2 == 1 {
+                let sibling = siblings.next().expect("proof must contain every sibling");
+                node = hash_node(sibling, &node);
+            } else if position + 1 < nodes {
+                let sibling = siblings.next().expect("proof must contain every sibling");
+                node = hash_node(&node, sibling);
+            }
+            position /= 2;
+            nodes = nodes.div_ceil(2);
+        }
+        hash_root(self.leaves, self.leaf_size, &node)
+    }
+
+    /// Checks whether the data of the leaf belongs to the tree with the given root.
+    ///
+    /// Every leaf except the last one must be exactly [`leaf_size`](Proof::leaf_size) bytes long, the last one must not
+    /// be longer and is empty only for an empty input. The root digests are compared in constant time.
+    #[must_use]
+    pub fn verify(&self, data: impl AsRef<[u8]>, root: &Digest) -> bool {
+        let data = data.as_ref();
+        let length = if self.index + 1 < self.leaves {
+            data.len() == self.leaf_size
+        } else {
+            data.len() <= self.leaf_size && (!data.is_empty() || self.leaves == 1)
+        };
+        // the length is public, so it can be checked before the constant-time comparison
+        length && self.root(&hash_leaf(data)).ct_eq(root)
+    }
+
+    /// Encodes the proof into bytes.
+    ///
+    /// Check [`Proof`] for the description of the encoding.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + 10 + 10 + 10 + self.siblings.len() * DIGEST_LENGTH_BYTES);
+        bytes.push(Self::VERSION);
+        write_varint(&mut bytes, self.leaf_size as u64);
+        write_varint(&mut bytes, self.leaves as u64);
+        write_varint(&mut bytes, self.index as u64);
+        for sibling in &self.siblings {
+            bytes.extend_from_slice(sibling.as_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes the proof from bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when the bytes are not a valid encoding of a proof.
+    pub fn from_bytes(bytes: &[u8]) -> result::Result<Self, Error> {
+        let (&version, mut bytes) = bytes.split_first().ok_or(Error::InvalidEncoding)?;
+        if version != Self::VERSION {
+            return Err(Error::UnsupportedVersion { version });
+        }
+        let leaf_size = read_varint(&mut bytes)?;
+        if leaf_size == 0 {
+            return Err(Error::InvalidLeafSize);
+        }
+        let leaves = read_varint(&mut bytes)?;
+        let index = read_varint(&mut bytes)?;
+        if index >= leaves {
+            return Err(Error::InvalidIndex { index, leaves });
+        }
+        // the ranges of all leaves must be representable
+        if (leaves as u64).checked_mul(leaf_size as u64).is_none() {
+            return Err(Error::InvalidSize { leaves, leaf_size });
+        }
+
+        let count = siblings(index, leaves);
+        let length = count * DIGEST_LENGTH_BYTES;
+        if bytes.len() != length {
+            let error = Error::InvalidLength {
+                value: bytes.len(),
+                proper: length,
+            };
+            return Err(error);
+        }
+        let siblings = bytes
+            .chunks_exact(DIGEST_LENGTH_BYTES)
+            .map(|bytes| Digest::new(bytes.try_into().expect("chunk must have exact length")))
+            .collect();
+        Ok(Self {
+            index,
+            leaves,
+            leaf_size,
+            siblings,
+        })
+    }
+}
+
+/// Returns the number of siblings on the path from the leaf to the root.
+fn siblings(mut index: usize, mut leaves: usize) -> usize {
+    let mut count = 0;
+    while leaves > 1 {
+        if index % 2 == 1 || index + 1 < leaves {
+            count += 1;
+        }
+        index /= 2;
+        leaves = leaves.div_ceil(2);
+    }
+    count
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        // the mask guarantees that the value fits into a byte
+        bytes.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> result::Result<usize, Error> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first().ok_or(Error::InvalidEncoding)?;
+        *bytes = rest;
+        let part = u64::from(byte & 0x7F);
+        if part << shift >> shift != part {
+            return Err(Error::InvalidEncoding);
+        }
+        value |= part << shift;
+        if byte & 0x80 == 0 {
+            // only the shortest encoding is accepted
+            if byte == 0 && shift > 0 {
+                return Err(Error::InvalidEncoding);
+            }
+            return usize::try_from(value).map_err(|_| Error::InvalidEncoding);
+        }
+    }
+    Err(Error::InvalidEncoding)
+}
+
+/// An error type for the proof decoding.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// Represents truncated or non-canonical bytes.
+    #[error("Improperly encoded proof")]
+    InvalidEncoding,
+    /// Represents an encoding version other than [`Proof::VERSION`].
+    #[error("Unsupported proof version `{version}`")]
+    UnsupportedVersion { version: u8 },
+    /// Represents a leaf size equal to zero.
+    #[error("Invalid leaf size `0`")]
+    InvalidLeafSize,
+    /// Represents leaves whose total size exceeds the maximum length of the input.
+    #[error("Invalid size of `{leaves}` leaves of `{leaf_size}` bytes")]
+    InvalidSize { leaves: usize, leaf_size: usize },
+    /// Represents an index of a leaf which doesn't exist.
+    #[error("Invalid leaf index `{index}`, number of leaves `{leaves}`")]
+    InvalidIndex { index: usize, leaves: usize },
+    /// Represents sibling digests of an unexpected total length.
+    #[error("Invalid length `{value}`, proper value `{proper}`")]
+    InvalidLength { value: usize, proper: usize },
+}
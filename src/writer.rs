@@ -2,6 +2,14 @@
 //!
 //! The [`Writer`] allows on-the-fly calculation of the digest while writing the data.
 //!
+//! The [`VerifyingWriter`] created by [`verifying`] also compares the digest with the expected one when it is
+//! finished, and fails with an [`io::Error`] of [`ErrorKind::InvalidData`] kind which wraps
+//! [`VerifyError::Mismatch`](crate::VerifyError::Mismatch) when they are different. The mismatch can be retrieved
+//! with [`VerifyError::from_io_error`](crate::VerifyError::from_io_error).
+//!
+//! The [`VerifyingFile`] created by [`verifying_file`] writes to a temporary file, which is renamed to the final path
+//! only when the digest matches.
+//!
 //! # Enabling
 //!
 //! Add the following entry to your `Cargo.toml` file to enable the `writer` feature:
@@ -42,13 +50,21 @@
 //! # }
 //! ```
 
-use std::io::Write;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "async-runtime-tokio")]
+use std::pin::Pin;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "async-runtime-tokio")]
+use std::task::{ready, Context, Poll};
 
 use chksum_writer as writer;
 #[cfg(feature = "async-runtime-tokio")]
 use tokio::io::AsyncWrite;
 
-use crate::SHA2_224;
+use crate::{verify_io, Digest, SHA2_224};
 
 /// A specialized [`Writer`](writer::Writer) type with the [`SHA2_224`] hash algorithm.
 pub type Writer<W> = writer::Writer<W, SHA2_224>;
@@ -78,3 +94,236 @@ pub fn async_new(inner: impl AsyncWrite) -> AsyncWriter<impl AsyncWrite> {
 pub fn async_with_hash(inner: impl AsyncWrite, hash: SHA2_224) -> AsyncWriter<impl AsyncWrite> {
     writer::async_with_hash(inner, hash)
 }
+
+/// Creates new [`VerifyingWriter`] which checks the digest of the data against the expected one.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Write;
+///
+/// use chksum_sha2_224 as sha2_224;
+/// use sha2_224::VerifyError;
+///
+/// let expected = sha2_224::hash(b"example data");
+/// let mut writer = sha2_224::writer::verifying(Vec::new(), expected);
+/// writer.write_all(b"example data").unwrap();
+/// assert_eq!(writer.finish().unwrap(), b"example data");
+///
+/// let mut writer = sha2_224::writer::verifying(Vec::new(), expected);
+/// writer.write_all(b"corrupted data").unwrap();
+/// let error = writer.finish().unwrap_err();
+/// assert!(matches!(
+///     VerifyError::from_io_error(&error),
+///     Some(VerifyError::Mismatch { .. })
+/// ));
+/// ```
+pub fn verifying<W: Write>(inner: W, expected: Digest) -> VerifyingWriter<W> {
+    VerifyingWriter::new(inner, expected)
+}
+
+/// Creates new [`VerifyingFile`] which writes to a temporary file next to the given path.
+///
+/// # Errors
+///
+/// Returns [`io::Error`] when the temporary file cannot be created.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::io::Write;
+///
+/// use chksum_sha2_224 as sha2_224;
+///
+/// # fn wrapper(path: &Path) -> std::io::Result<()> {
+/// let expected = sha2_224::hash(b"example data");
+/// let mut file = sha2_224::writer::verifying_file(path, expected)?;
+/// file.write_all(b"example data")?;
+/// file.finish()?;
+/// assert_eq!(std::fs::read(path)?, b"example data");
+/// # Ok(())
+/// # }
+/// ```
+pub fn verifying_file(path: impl AsRef<Path>, expected: Digest) -> io::Result<VerifyingFile> {
+    VerifyingFile::create(path, expected)
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Creates new [`AsyncVerifyingWriter`] which checks the digest of the data against the expected one.
+pub fn async_verifying<W: AsyncWrite + Unpin>(inner: W, expected: Digest) -> AsyncVerifyingWriter<W> {
+    AsyncVerifyingWriter::new(inner, expected)
+}
+
+/// A writer which verifies the digest of the data when it is finished.
+#[derive(Debug)]
+pub struct VerifyingWriter<W> {
+    inner: W,
+    hash: SHA2_224,
+    expected: Digest,
+}
+
+impl<W: Write> VerifyingWriter<W> {
+    /// Creates new writer.
+    pub fn new(inner: W, expected: Digest) -> Self {
+        let hash = SHA2_224::new();
+        Self { inner, hash, expected }
+    }
+
+    /// Flushes the inner writer, verifies the digest of the written data and returns the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::Error`] when flushing fails or when the digest doesn't match.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.flush()?;
+        verify_io(&self.hash, &self.expected)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for VerifyingWriter<W> {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        let length = self.inner.write(buffer)?;
+        self.hash.update(&buffer[..length]);
+        Ok(length)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A file which is written to a temporary path and renamed to the final path only when the digest matches.
+///
+/// The temporary file is created in the same directory as the final one, so the rename is atomic on most file
+/// systems. It is removed when the digest doesn't match or when the file is dropped without being finished.
+#[derive(Debug)]
+pub struct VerifyingFile {
+    writer: Option<VerifyingWriter<File>>,
+    temporary: PathBuf,
+    path: PathBuf,
+}
+
+impl VerifyingFile {
+    /// Creates a temporary file next to the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::Error`] when the temporary file cannot be created.
+    pub fn create(path: impl AsRef<Path>, expected: Digest) -> io::Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let path = path.as_ref().to_path_buf();
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "Path without a file name"))?;
+        let name = name.to_string_lossy();
+        loop {
+            // the counter makes names unique within the process, leftovers of other processes are skipped
+            let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let temporary = path.with_file_name(format!(".{name}.{}.{counter}.tmp", process::id()));
+            match OpenOptions::new().write(true).create_new(true).open(&temporary) {
+                Ok(file) => {
+                    let writer = Some(VerifyingWriter::new(file, expected));
+                    return Ok(Self {
+                        writer,
+                        temporary,
+                        path,
+                    });
+                },
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => {},
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Returns the path of the temporary file.
+    #[must_use]
+    pub fn temporary_path(&self) -> &Path {
+        &self.temporary
+    }
+
+    /// Syncs the temporary file, verifies the digest of the written data and renames the file to the final path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::Error`] when syncing or renaming fails or when the digest doesn't match, the temporary file is
+    /// removed in such case.
+    pub fn finish(mut self) -> io::Result<File> {
+        let writer = self.writer.take().expect("writer must be present until finished");
+        let result = writer
+            .finish()
+            .and_then(|file| file.sync_all().map(|()| file))
+            .and_then(|file| fs::rename(&self.temporary, &self.path).map(|()| file));
+        if result.is_err() {
+            let _ = fs::remove_file(&self.temporary);
+        }
+        result
+    }
+
+    fn writer(&mut self) -> &mut VerifyingWriter<File> {
+        self.writer.as_mut().expect("writer must be present until finished")
+    }
+}
+
+impl Write for VerifyingFile {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        self.writer().write(buffer)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer().flush()
+    }
+}
+
+impl Drop for VerifyingFile {
+    fn drop(&mut self) {
+        if self.writer.take().is_some() {
+            let _ = fs::remove_file(&self.temporary);
+        }
+    }
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// An asynchronous writer which verifies the digest of the data when it is shut down.
+#[derive(Debug)]
+pub struct AsyncVerifyingWriter<W> {
+    inner: W,
+    hash: SHA2_224,
+    expected: Digest,
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+impl<W: AsyncWrite + Unpin> AsyncVerifyingWriter<W> {
+    /// Creates new writer.
+    pub fn new(inner: W, expected: Digest) -> Self {
+        let hash = SHA2_224::new();
+        Self { inner, hash, expected }
+    }
+
+    /// Returns the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncVerifyingWriter<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buffer: &[u8]) -> Poll<io::Result<usize>> {
+        let Self { inner, hash, .. } = self.get_mut();
+        let length = ready!(Pin::new(inner).poll_write(cx, buffer))?;
+        hash.update(&buffer[..length]);
+        Poll::Ready(Ok(length))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let Self { inner, hash, expected } = self.get_mut();
+        ready!(Pin::new(inner).poll_shutdown(cx))?;
+        Poll::Ready(verify_io(hash, expected))
+    }
+}
@@ -0,0 +1,218 @@
+use std::fs::File;
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_224::dir::DirHasher;
+use chksum_sha2_224::{chksum_with, hash, CancellationToken, Cancelled, ChksumOptions, Error as ChksumError};
+
+mod common;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[test]
+fn token() {
+    let token = CancellationToken::new();
+    let clone = token.clone();
+    assert!(!token.is_cancelled());
+
+    clone.cancel();
+    assert!(token.is_cancelled());
+    assert!(clone.is_cancelled());
+}
+
+#[test]
+fn cancelled_error() {
+    let error = ChksumError::Io(IoError::other(Cancelled));
+    assert!(Cancelled::matches(&error));
+    assert_eq!(error.to_string(), "Operation cancelled");
+    assert!(!Cancelled::matches(&ChksumError::IsTerminal));
+    assert!(!Cancelled::matches(&ChksumError::Io(IoError::other("other"))));
+}
+
+#[test]
+fn not_cancelled() -> Result<(), Error> {
+    let data = common::data(100_000);
+
+    let mut options = ChksumOptions::new().cancellation(CancellationToken::new());
+    assert_eq!(chksum_with(&data[..], &mut options)?, hash(&data));
+
+    Ok(())
+}
+
+#[test]
+fn cancelled_before_start() {
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let mut options = ChksumOptions::new().cancellation(token);
+    let error = chksum_with(&b"data"[..], &mut options).unwrap_err();
+    assert!(Cancelled::matches(&error));
+}
+
+#[test]
+fn cancelled_mid_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    file.write_binary(&common::data(100_000))?;
+
+    let token = CancellationToken::new();
+    let mut chunks = 0;
+    let mut options = ChksumOptions::new()
+        .buffer_size(1000)
+        .cancellation(token.clone())
+        .progress(|progress| {
+            chunks += 1;
+            if progress.processed() >= 5000 {
+                token.cancel();
+            }
+        });
+    let error = chksum_with(File::open(file.path())?, &mut options).unwrap_err();
+    drop(options);
+    assert!(Cancelled::matches(&error));
+    assert_eq!(chunks, 5);
+
+    Ok(())
+}
+
+#[test]
+fn cancelled_mid_directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    for index in 0..20 {
+        temp_dir
+            .child(format!("file-{index:02}.bin"))
+            .write_binary(&common::data(100_000))?;
+    }
+
+    for threads in [1, 2, 4] {
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        let hasher = DirHasher::new(temp_dir.path())
+            .threads(threads)
+            .cancellation(token.clone())
+            .progress(move |progress| {
+                if progress.path().is_some_and(|path| path.ends_with("file-03.bin")) {
+                    cancel.cancel();
+                }
+            });
+        assert!(!token.is_cancelled());
+        let error = hasher.chksum().unwrap_err();
+        assert!(Cancelled::matches(&error));
+        assert!(token.is_cancelled());
+        assert!(Cancelled::matches(&hasher.manifest().unwrap_err()));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn cancelled_directory_before_start() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("file.txt").write_binary(b"data")?;
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let error = DirHasher::new(temp_dir.path())
+        .cancellation(token)
+        .chksum()
+        .unwrap_err();
+    assert!(Cancelled::matches(&error));
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_cancelled_mid_file() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use chksum_sha2_224::async_chksum_with;
+
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.bin");
+        file.write_binary(&common::data(100_000))?;
+
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        let mut options = ChksumOptions::new()
+            .buffer_size(1000)
+            .cancellation(token)
+            .progress(move |_| cancel.cancel());
+        let file = tokio::fs::File::open(file.path()).await?;
+        let error = async_chksum_with(file, &mut options).await.unwrap_err();
+        assert!(Cancelled::matches(&error));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_cancelled_mid_directory() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use std::sync::{Arc, Mutex};
+
+        let temp_dir = TempDir::new()?;
+        for index in 0..20 {
+            temp_dir
+                .child(format!("file-{index:02}.bin"))
+                .write_binary(&common::data(100_000))?;
+        }
+
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let collected = Arc::clone(&reports);
+        let hasher = DirHasher::new(temp_dir.path())
+            .cancellation(token.clone())
+            .progress(move |progress| {
+                let path = progress.path().expect("path must be reported").to_path_buf();
+                if path.ends_with("file-03.bin") {
+                    cancel.cancel();
+                }
+                collected.lock().unwrap().push(path);
+            });
+        let error = hasher.async_chksum().await.unwrap_err();
+        assert!(Cancelled::matches(&error));
+        assert!(token.is_cancelled());
+
+        // the token is checked before the first chunk of the file
+        let reports = reports.lock().unwrap();
+        assert!(reports.last().is_some_and(|path| path.ends_with("file-03.bin")));
+        assert_eq!(reports.iter().filter(|path| path.ends_with("file-03.bin")).count(), 1);
+        assert!(!reports.iter().any(|path| path.ends_with("file-04.bin")));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_cancelled_directory_before_start() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("file.txt").write_binary(b"data")?;
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let error = DirHasher::new(temp_dir.path())
+            .cancellation(token)
+            .async_chksum()
+            .await
+            .unwrap_err();
+        assert!(Cancelled::matches(&error));
+    }
+
+    Ok(())
+}
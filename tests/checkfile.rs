@@ -0,0 +1,287 @@
+use std::path::PathBuf;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_224::checkfile::{self, Entry, Mode, ParseError, Status};
+use chksum_sha2_224::{Error as ChksumError, FormatError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+}
+
+const EMPTY_DIGEST: &str = "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f";
+const DATA_DIGEST: &str = "f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769";
+
+#[test]
+fn entry_to_line() {
+    let digest = chksum_sha2_224::hash(b"data");
+    assert_eq!(
+        Entry::new(digest, "file.txt", Mode::Text).to_string(),
+        format!("{DATA_DIGEST}  file.txt")
+    );
+    assert_eq!(
+        Entry::new(digest, "file.txt", Mode::Binary).to_string(),
+        format!("{DATA_DIGEST} *file.txt")
+    );
+    assert_eq!(
+        Entry::new(digest, "file.txt", Mode::Tag).to_string(),
+        format!("SHA224 (file.txt) = {DATA_DIGEST}")
+    );
+}
+
+#[test]
+fn entry_to_line_escaped() {
+    let digest = chksum_sha2_224::hash(b"data");
+    let entry = Entry::new(digest, "new\nline\\back\rslash", Mode::Text);
+    assert_eq!(
+        entry.to_string(),
+        format!("\\{DATA_DIGEST}  new\\nline\\\\back\\rslash")
+    );
+    assert_eq!(entry.to_line(false), format!("{DATA_DIGEST}  new\nline\\back\rslash"));
+
+    let entry = Entry::new(digest, "new\nline", Mode::Tag);
+    assert_eq!(entry.to_string(), format!("\\SHA224 (new\\nline) = {DATA_DIGEST}"));
+}
+
+#[test]
+fn parse_line() {
+    let entry = checkfile::parse_line(&format!("{DATA_DIGEST}  file.txt\n")).unwrap();
+    assert_eq!(
+        entry,
+        Entry::new(chksum_sha2_224::hash(b"data"), "file.txt", Mode::Text)
+    );
+
+    let entry = checkfile::parse_line(&format!("{DATA_DIGEST} *dir/file name.txt\r\n")).unwrap();
+    assert_eq!(
+        entry,
+        Entry::new(chksum_sha2_224::hash(b"data"), "dir/file name.txt", Mode::Binary)
+    );
+
+    let entry: Entry = format!("SHA224 (file (1).txt) = {}", DATA_DIGEST.to_uppercase())
+        .parse()
+        .unwrap();
+    assert_eq!(
+        entry,
+        Entry::new(chksum_sha2_224::hash(b"data"), "file (1).txt", Mode::Tag)
+    );
+
+    let entry = checkfile::parse_line(&format!("  {EMPTY_DIGEST}  file (1).txt")).unwrap();
+    assert_eq!(
+        entry,
+        Entry::new(chksum_sha2_224::hash(b""), "file (1).txt", Mode::Text)
+    );
+}
+
+#[test]
+fn parse_line_round_trip() {
+    let digest = chksum_sha2_224::hash(b"data");
+    for mode in [Mode::Text, Mode::Binary, Mode::Tag] {
+        for path in [
+            "file.txt",
+            " leading space",
+            "*star",
+            "new\nline",
+            "back\\slash",
+            "carriage\rreturn",
+        ] {
+            let entry = Entry::new(digest, path, mode);
+            assert_eq!(checkfile::parse_line(&entry.to_string()).unwrap(), entry);
+        }
+    }
+}
+
+#[test]
+fn parse_line_malformed() {
+    assert_eq!(checkfile::parse_line(""), Err(ParseError::InvalidFormat));
+    assert_eq!(
+        checkfile::parse_line("d14a028c file.txt"),
+        Err(ParseError::InvalidFormat)
+    );
+    assert_eq!(
+        checkfile::parse_line(&format!("{DATA_DIGEST} file.txt")),
+        Err(ParseError::InvalidFormat)
+    );
+    assert_eq!(
+        checkfile::parse_line(&format!("{DATA_DIGEST}  ")),
+        Err(ParseError::InvalidFormat)
+    );
+    assert_eq!(
+        checkfile::parse_line(&format!("SHA224 (file.txt) {DATA_DIGEST}")),
+        Err(ParseError::InvalidFormat)
+    );
+    assert_eq!(
+        checkfile::parse_line(&format!("SHA256 (file.txt) = {DATA_DIGEST}")),
+        Err(ParseError::UnsupportedAlgorithm {
+            algorithm: String::from("SHA256")
+        })
+    );
+    assert_eq!(
+        checkfile::parse_line(&format!("\\{DATA_DIGEST}  file\\t.txt")),
+        Err(ParseError::InvalidEscape)
+    );
+    assert_eq!(
+        checkfile::parse_line("z14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f  file.txt"),
+        Err(ParseError::InvalidDigest(FormatError::InvalidCharacter {
+            character: 'z',
+            index: 0
+        }))
+    );
+}
+
+#[test]
+fn write_and_check() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let first = temp_dir.child("first.txt");
+    first.write_binary(b"data")?;
+    let second = temp_dir.child("second.txt");
+    second.write_binary(b"")?;
+
+    for mode in [Mode::Text, Mode::Binary, Mode::Tag] {
+        let mut manifest = Vec::new();
+        checkfile::write(&mut manifest, [first.path(), second.path()], mode)?;
+
+        let report = checkfile::check(manifest.as_slice())?;
+        assert!(report.is_ok());
+        assert_eq!(report.checked.len(), 2);
+        assert_eq!(report.checked[0].line, 1);
+        assert_eq!(report.checked[0].path, first.path());
+        assert_eq!(report.checked[1].line, 2);
+        assert_eq!(report.checked[1].path, second.path());
+        assert!(report.malformed.is_empty());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn check_failed_missing_and_malformed() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(b"data")?;
+    let missing = temp_dir.child("missing.txt");
+
+    let manifest = format!(
+        "# comment\n{DATA_DIGEST}  {file}\n\n{EMPTY_DIGEST}  {file}\nimproperly formatted line\nSHA224 ({missing}) = \
+         {DATA_DIGEST}\n",
+        file = file.path().display(),
+        missing = missing.path().display(),
+    );
+    let report = checkfile::check(manifest.as_bytes())?;
+    assert!(!report.is_ok());
+    assert_eq!(report.failed(), 1);
+    assert_eq!(report.missing(), 1);
+
+    let statuses: Vec<_> = report
+        .checked
+        .iter()
+        .map(|checked| (checked.line, checked.path.clone()))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            (2, file.to_path_buf()),
+            (4, file.to_path_buf()),
+            (6, PathBuf::from(missing.path()))
+        ]
+    );
+    assert!(matches!(report.checked[0].status, Status::Ok));
+    assert!(matches!(report.checked[1].status, Status::Failed));
+    assert!(matches!(report.checked[2].status, Status::Missing(ChksumError::Io(_))));
+
+    assert_eq!(report.malformed.len(), 1);
+    assert_eq!(report.malformed[0].line, 5);
+    assert_eq!(report.malformed[0].error, ParseError::InvalidFormat);
+
+    Ok(())
+}
+
+#[test]
+fn lines() -> Result<(), Error> {
+    let manifest =
+        format!("# comment\n{DATA_DIGEST}  file.txt\n\nimproperly formatted line\n{EMPTY_DIGEST} *empty.txt\n");
+    let lines = checkfile::lines(manifest.as_bytes()).collect::<Result<Vec<_>, _>>()?;
+    let lines: Vec<_> = lines
+        .into_iter()
+        .map(|(number, entry)| (number, entry.map(|entry| entry.path)))
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            (2, Ok(PathBuf::from("file.txt"))),
+            (4, Err(ParseError::InvalidFormat)),
+            (5, Ok(PathBuf::from("empty.txt")))
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn check_without_entries() -> Result<(), Error> {
+    let report = checkfile::check("# only a comment\n\n".as_bytes())?;
+    assert!(!report.is_ok());
+    assert!(report.checked.is_empty());
+    assert!(report.malformed.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn check_directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("file.txt").write_binary(b"data")?;
+
+    let mut manifest = Vec::new();
+    let error = checkfile::write(&mut manifest, [temp_dir.path()], Mode::Text).unwrap_err();
+    assert_eq!(error.to_string(), "Is a directory");
+    assert!(manifest.is_empty());
+
+    // lines written by other tools are reported as missing
+    let digest = chksum_sha2_224::chksum(temp_dir.path())?;
+    let mut manifest = Entry::new(digest, temp_dir.path(), Mode::Text).to_bytes(true);
+    manifest.push(b'\n');
+    let report = checkfile::check(manifest.as_slice())?;
+    assert!(!report.is_ok());
+    assert_eq!(report.missing(), 1);
+    let Status::Missing(ChksumError::Io(error)) = &report.checked[0].status else {
+        panic!("directory must be reported as missing");
+    };
+    assert_eq!(error.to_string(), "Is a directory");
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_paths() -> Result<(), Error> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child(OsStr::from_bytes(b"bad\xFF"));
+    file.write_binary(b"data")?;
+
+    for mode in [Mode::Text, Mode::Binary, Mode::Tag] {
+        let mut manifest = Vec::new();
+        checkfile::write(&mut manifest, [file.path()], mode)?;
+        let needle: &[u8] = b"/bad\xFF";
+        assert!(manifest.windows(needle.len()).any(|window| window == needle));
+
+        let report = checkfile::check(manifest.as_slice())?;
+        assert!(report.is_ok());
+        assert_eq!(report.checked[0].path, file.path());
+    }
+
+    let line = [format!("{DATA_DIGEST}  ").as_bytes(), b"bad\xFF"].concat();
+    let entry = checkfile::parse_bytes(&line).unwrap();
+    assert_eq!(entry.path.as_os_str().as_bytes(), b"bad\xFF");
+    assert_eq!(entry.to_bytes(true), line);
+    assert_eq!(entry.to_line(true), format!("{DATA_DIGEST}  bad\u{FFFD}"));
+
+    Ok(())
+}
@@ -0,0 +1,366 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use assert_fs::fixture::{ChildPath, FixtureError};
+use assert_fs::prelude::{FileTouch, FileWriteBin, FileWriteStr, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+
+const EMPTY_DIGEST: &str = "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f";
+const DATA_DIGEST: &str = "f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769";
+
+fn sha224sum(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sha224sum"))
+        .current_dir(dir)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .expect("binary must be executable")
+}
+
+fn sha224sum_with_stdin(dir: &Path, args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sha224sum"))
+        .current_dir(dir)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("binary must be executable");
+    child
+        .stdin
+        .take()
+        .expect("stdin must be piped")
+        .write_all(stdin)
+        .expect("stdin must be writable");
+    child.wait_with_output().expect("binary must finish")
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).expect("stdout must be valid UTF-8")
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).expect("stderr must be valid UTF-8")
+}
+
+fn fixture() -> Result<(TempDir, ChildPath, ChildPath), FixtureError> {
+    let temp_dir = TempDir::new()?;
+    let empty = temp_dir.child("empty.txt");
+    empty.touch()?;
+    let data = temp_dir.child("data.txt");
+    data.write_binary(b"data")?;
+    Ok((temp_dir, empty, data))
+}
+
+#[test]
+fn hash_files() -> Result<(), FixtureError> {
+    let (temp_dir, _, _) = fixture()?;
+
+    let output = sha224sum(&temp_dir, &["empty.txt", "data.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        format!("{EMPTY_DIGEST}  empty.txt\n{DATA_DIGEST}  data.txt\n")
+    );
+
+    let output = sha224sum(&temp_dir, &["--binary", "data.txt"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{DATA_DIGEST} *data.txt\n"));
+
+    Ok(())
+}
+
+#[test]
+fn hash_tag_and_zero() -> Result<(), FixtureError> {
+    let (temp_dir, _, _) = fixture()?;
+
+    let output = sha224sum(&temp_dir, &["--tag", "data.txt"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("SHA224 (data.txt) = {DATA_DIGEST}\n"));
+
+    let output = sha224sum(&temp_dir, &["-z", "--tag", "data.txt", "empty.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        format!("SHA224 (data.txt) = {DATA_DIGEST}\0SHA224 (empty.txt) = {EMPTY_DIGEST}\0")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn hash_stdin() -> Result<(), FixtureError> {
+    let temp_dir = TempDir::new()?;
+
+    let output = sha224sum_with_stdin(&temp_dir, &[], b"data");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{DATA_DIGEST}  -\n"));
+
+    let output = sha224sum_with_stdin(&temp_dir, &["-"], b"");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{EMPTY_DIGEST}  -\n"));
+
+    Ok(())
+}
+
+#[test]
+fn hash_directory() -> Result<(), FixtureError> {
+    let temp_dir = TempDir::new()?;
+    let dir = temp_dir.child("dir");
+    dir.create_dir_all()?;
+    dir.child("file.txt").write_binary(b"data")?;
+
+    let output = sha224sum(&temp_dir, &["dir"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).contains("dir: Is a directory"));
+
+    let output = sha224sum(&temp_dir, &["--recursive", "dir"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{DATA_DIGEST}  dir\n"));
+
+    Ok(())
+}
+
+#[test]
+fn hash_missing_file() -> Result<(), FixtureError> {
+    let (temp_dir, _, _) = fixture()?;
+
+    let output = sha224sum(&temp_dir, &["missing.txt", "data.txt"]);
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), format!("{DATA_DIGEST}  data.txt\n"));
+    assert!(stderr(&output).starts_with("sha224sum: missing.txt: "));
+
+    Ok(())
+}
+
+#[test]
+fn check_ok() -> Result<(), FixtureError> {
+    let (temp_dir, _, _) = fixture()?;
+    let manifest = temp_dir.child("SHA224SUMS");
+    manifest.write_str(&format!(
+        "{EMPTY_DIGEST}  empty.txt\nSHA224 (data.txt) = {DATA_DIGEST}\n"
+    ))?;
+
+    let output = sha224sum(&temp_dir, &["--check", "SHA224SUMS"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "empty.txt: OK\ndata.txt: OK\n");
+    assert!(stderr(&output).is_empty());
+
+    let output = sha224sum(&temp_dir, &["-c", "--quiet", "SHA224SUMS"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).is_empty());
+
+    let output = sha224sum_with_stdin(&temp_dir, &["-c"], format!("{DATA_DIGEST} *data.txt\n").as_bytes());
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "data.txt: OK\n");
+
+    Ok(())
+}
+
+#[test]
+fn check_generated_manifest() -> Result<(), FixtureError> {
+    let (temp_dir, _, _) = fixture()?;
+
+    let output = sha224sum(&temp_dir, &["--tag", "empty.txt", "data.txt"]);
+    assert!(output.status.success());
+    let manifest = temp_dir.child("SHA224SUMS");
+    manifest.write_binary(&output.stdout)?;
+
+    let output = sha224sum(&temp_dir, &["-c", "SHA224SUMS"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "empty.txt: OK\ndata.txt: OK\n");
+
+    Ok(())
+}
+
+#[test]
+fn check_failed_and_missing() -> Result<(), FixtureError> {
+    let (temp_dir, _, _) = fixture()?;
+    let manifest = temp_dir.child("SHA224SUMS");
+    manifest.write_str(&format!(
+        "{DATA_DIGEST}  empty.txt\n{DATA_DIGEST}  data.txt\n{DATA_DIGEST}  missing.txt\n"
+    ))?;
+
+    let output = sha224sum(&temp_dir, &["-c", "SHA224SUMS"]);
+    assert!(!output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "empty.txt: FAILED\ndata.txt: OK\nmissing.txt: FAILED open or read\n"
+    );
+    let stderr = stderr(&output);
+    assert!(stderr.contains("WARNING: 1 listed file could not be read"));
+    assert!(stderr.contains("WARNING: 1 computed checksum did NOT match"));
+
+    let output = sha224sum(&temp_dir, &["-c", "--status", "SHA224SUMS"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).is_empty());
+    assert!(output.stderr.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn check_ignore_missing() -> Result<(), FixtureError> {
+    let (temp_dir, _, _) = fixture()?;
+    let manifest = temp_dir.child("SHA224SUMS");
+    manifest.write_str(&format!("{DATA_DIGEST}  data.txt\n{DATA_DIGEST}  missing.txt\n"))?;
+
+    let output = sha224sum(&temp_dir, &["-c", "--ignore-missing", "SHA224SUMS"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "data.txt: OK\n");
+
+    manifest.write_str(&format!("{DATA_DIGEST}  missing.txt\n"))?;
+    let output = sha224sum(&temp_dir, &["-c", "--ignore-missing", "SHA224SUMS"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("no file was verified"));
+
+    Ok(())
+}
+
+#[test]
+fn check_malformed_lines() -> Result<(), FixtureError> {
+    let (temp_dir, _, _) = fixture()?;
+    let manifest = temp_dir.child("SHA224SUMS");
+    manifest.write_str(&format!("{DATA_DIGEST}  data.txt\nnot a checksum line\n"))?;
+
+    let output = sha224sum(&temp_dir, &["-c", "SHA224SUMS"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "data.txt: OK\n");
+    assert!(stderr(&output).contains("WARNING: 1 line is improperly formatted"));
+
+    let output = sha224sum(&temp_dir, &["-c", "--warn", "SHA224SUMS"]);
+    assert!(output.status.success());
+    assert!(stderr(&output).contains("SHA224SUMS: 2: improperly formatted SHA224 checksum line"));
+
+    let output = sha224sum(&temp_dir, &["-c", "--strict", "SHA224SUMS"]);
+    assert!(!output.status.success());
+
+    manifest.write_str("not a checksum line\n")?;
+    let output = sha224sum(&temp_dir, &["-c", "SHA224SUMS"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("SHA224SUMS: no properly formatted checksum lines found"));
+
+    Ok(())
+}
+
+#[test]
+fn invalid_options() -> Result<(), FixtureError> {
+    let temp_dir = TempDir::new()?;
+
+    for args in [
+        &["--unknown"][..],
+        &["-x"],
+        &["--quiet"],
+        &["--status"],
+        &["--strict"],
+        &["--check", "--tag"],
+        &["--tag", "--text"],
+    ] {
+        let output = sha224sum(&temp_dir, args);
+        assert!(!output.status.success(), "{args:?}");
+        assert!(stderr(&output).contains("Try 'sha224sum --help'"), "{args:?}");
+    }
+
+    let output = sha224sum(&temp_dir, &["--help"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Usage: sha224sum"));
+
+    Ok(())
+}
+
+#[test]
+fn check_directory() -> Result<(), FixtureError> {
+    let (temp_dir, _, _) = fixture()?;
+    let dir = temp_dir.child("dir");
+    dir.create_dir_all()?;
+    dir.child("file.txt").write_binary(b"data")?;
+
+    let output = sha224sum(&temp_dir, &["-r", "dir"]);
+    let manifest = temp_dir.child("SHA224SUMS");
+    manifest.write_binary(&output.stdout)?;
+
+    let output = sha224sum(&temp_dir, &["-c", "SHA224SUMS"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "dir: OK\n");
+
+    dir.child("file.txt").write_binary(b"other data")?;
+    let output = sha224sum(&temp_dir, &["-c", "SHA224SUMS"]);
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), "dir: FAILED\n");
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_file_name() -> Result<(), FixtureError> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let temp_dir = TempDir::new()?;
+    let name = OsStr::from_bytes(b"bad\xFF.txt");
+    temp_dir.child(name).write_binary(b"data")?;
+
+    let hash = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_sha224sum"))
+            .current_dir(&temp_dir)
+            .args(args)
+            .arg(name)
+            .output()
+            .expect("binary must be executable")
+    };
+
+    let output = hash(&[]);
+    assert!(output.status.success());
+    assert_eq!(
+        output.stdout,
+        [format!("{DATA_DIGEST}  ").as_bytes(), b"bad\xFF.txt\n"].concat()
+    );
+    let output = sha224sum_with_stdin(&temp_dir, &["-c"], &output.stdout);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"bad\xFF.txt: OK\n");
+
+    let output = hash(&["--tag"]);
+    assert!(output.status.success());
+    assert_eq!(
+        output.stdout,
+        [b"SHA224 (bad\xFF.txt) = ", DATA_DIGEST.as_bytes(), b"\n"].concat()
+    );
+    let output = sha224sum_with_stdin(&temp_dir, &["-c"], &output.stdout);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"bad\xFF.txt: OK\n");
+
+    Ok(())
+}
+
+#[test]
+fn check_streams_results() -> Result<(), FixtureError> {
+    let (temp_dir, _, _) = fixture()?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sha224sum"))
+        .current_dir(&temp_dir)
+        .arg("-c")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("binary must be executable");
+    let mut stdin = child.stdin.take().expect("stdin must be piped");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout must be piped"));
+
+    // the result of the first line is printed before the rest of the manifest is written
+    let mut line = String::new();
+    writeln!(stdin, "{DATA_DIGEST}  data.txt").expect("stdin must be writable");
+    stdout.read_line(&mut line).expect("stdout must be readable");
+    assert_eq!(line, "data.txt: OK\n");
+
+    line.clear();
+    writeln!(stdin, "{EMPTY_DIGEST}  empty.txt").expect("stdin must be writable");
+    drop(stdin);
+    stdout.read_line(&mut line).expect("stdout must be readable");
+    assert_eq!(line, "empty.txt: OK\n");
+    assert!(child.wait().expect("binary must finish").success());
+
+    Ok(())
+}
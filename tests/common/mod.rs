@@ -0,0 +1,4 @@
+/// Returns test data of the given length which doesn't repeat within a block.
+pub fn data(length: usize) -> Vec<u8> {
+    (0..length).map(|value| (value * 31 % 251) as u8).collect()
+}
@@ -0,0 +1,192 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chksum_sha2_224::{Digest, FormatError};
+
+#[test]
+fn try_from_hex_lowercase() {
+    let digest = Digest::try_from_hex("d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f").unwrap();
+    #[rustfmt::skip]
+    assert_eq!(
+        digest.as_bytes(),
+        [
+            0xD1, 0x4A, 0x02, 0x8C,
+            0x2A, 0x3A, 0x2B, 0xC9,
+            0x47, 0x61, 0x02, 0xBB,
+            0x28, 0x82, 0x34, 0xC4,
+            0x15, 0xA2, 0xB0, 0x1F,
+            0x82, 0x8E, 0xA6, 0x2A,
+            0xC5, 0xB3, 0xE4, 0x2F,
+        ]
+    );
+}
+
+#[test]
+fn try_from_hex_uppercase_and_mixed_case() {
+    let lowercase = Digest::try_from_hex("d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f").unwrap();
+    let uppercase = Digest::try_from_hex("D14A028C2A3A2BC9476102BB288234C415A2B01F828EA62AC5B3E42F").unwrap();
+    let mixed = Digest::try_from_hex("D14a028C2a3A2bC9476102Bb288234c415A2b01F828eA62aC5b3E42f").unwrap();
+    assert_eq!(lowercase.as_bytes(), uppercase.as_bytes());
+    assert_eq!(lowercase.as_bytes(), mixed.as_bytes());
+}
+
+#[test]
+fn from_str_and_try_from() {
+    let digest: Digest = "f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        digest.to_hex_lowercase(),
+        "f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769"
+    );
+
+    let digest = Digest::try_from("f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769").unwrap();
+    assert_eq!(
+        digest.to_hex_lowercase(),
+        "f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769"
+    );
+}
+
+#[test]
+fn round_trip_with_formatters() {
+    let digest = chksum_sha2_224::hash(b"data");
+    assert_eq!(
+        Digest::try_from_hex(&digest.to_string()).unwrap().as_bytes(),
+        digest.as_bytes()
+    );
+    assert_eq!(
+        Digest::try_from_hex(&format!("{digest:x}")).unwrap().as_bytes(),
+        digest.as_bytes()
+    );
+    assert_eq!(
+        Digest::try_from_hex(&format!("{digest:X}")).unwrap().as_bytes(),
+        digest.as_bytes()
+    );
+    assert_eq!(
+        Digest::try_from_hex(&digest.to_hex_uppercase()).unwrap().as_bytes(),
+        digest.as_bytes()
+    );
+}
+
+#[test]
+fn invalid_length() {
+    assert_eq!(
+        Digest::try_from_hex("").err(),
+        Some(FormatError::InvalidLength { value: 0, proper: 56 })
+    );
+    assert_eq!(
+        Digest::try_from_hex("d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42").err(),
+        Some(FormatError::InvalidLength { value: 55, proper: 56 })
+    );
+    assert_eq!(
+        Digest::try_from_hex("d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f0").err(),
+        Some(FormatError::InvalidLength { value: 57, proper: 56 })
+    );
+}
+
+#[test]
+fn invalid_character() {
+    assert_eq!(
+        Digest::try_from_hex("g14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f").err(),
+        Some(FormatError::InvalidCharacter {
+            character: 'g',
+            index: 0
+        })
+    );
+    assert_eq!(
+        Digest::try_from_hex("+14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f").err(),
+        Some(FormatError::InvalidCharacter {
+            character: '+',
+            index: 0
+        })
+    );
+    assert_eq!(
+        Digest::try_from_hex("d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e4 f").err(),
+        Some(FormatError::InvalidCharacter {
+            character: ' ',
+            index: 54
+        })
+    );
+    assert_eq!(
+        Digest::try_from_hex("d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e4ż").err(),
+        Some(FormatError::InvalidCharacter {
+            character: 'ż',
+            index: 54
+        })
+    );
+}
+
+#[test]
+fn clone_copy_and_debug() {
+    let digest = chksum_sha2_224::hash(b"data");
+    let copy = digest;
+    #[allow(clippy::clone_on_copy)]
+    let clone = digest.clone();
+    assert_eq!(digest, copy);
+    assert_eq!(digest, clone);
+    assert!(!format!("{digest:?}").is_empty());
+}
+
+#[test]
+fn equality() {
+    let digest = chksum_sha2_224::hash(b"data");
+    assert_eq!(digest, chksum_sha2_224::hash(b"data"));
+    assert_ne!(digest, chksum_sha2_224::hash(b"other data"));
+}
+
+#[test]
+fn equality_with_bytes() {
+    let digest = chksum_sha2_224::hash(b"");
+    #[rustfmt::skip]
+    let bytes = [
+        0xD1, 0x4A, 0x02, 0x8C,
+        0x2A, 0x3A, 0x2B, 0xC9,
+        0x47, 0x61, 0x02, 0xBB,
+        0x28, 0x82, 0x34, 0xC4,
+        0x15, 0xA2, 0xB0, 0x1F,
+        0x82, 0x8E, 0xA6, 0x2A,
+        0xC5, 0xB3, 0xE4, 0x2F,
+    ];
+    assert!(digest == bytes[..]);
+    assert!(digest != bytes[..27]);
+    assert!(digest != [0u8; 28][..]);
+}
+
+#[test]
+fn equality_with_hex_string() {
+    let digest = chksum_sha2_224::hash(b"");
+    assert_eq!(digest, "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f");
+    assert_eq!(digest, "D14A028C2A3A2BC9476102BB288234C415A2B01F828EA62AC5B3E42F");
+    assert_ne!(digest, "f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769");
+    assert_ne!(digest, "d14a028c");
+    assert_ne!(digest, "not a digest");
+}
+
+#[test]
+fn hash_map_and_hash_set() {
+    let mut map = HashMap::new();
+    map.insert(chksum_sha2_224::hash(b""), "empty");
+    map.insert(chksum_sha2_224::hash(b"data"), "data");
+    assert_eq!(map.get(&chksum_sha2_224::hash(b"")), Some(&"empty"));
+    assert_eq!(map.get(&chksum_sha2_224::hash(b"data")), Some(&"data"));
+    assert_eq!(map.get(&chksum_sha2_224::hash(b"other data")), None);
+
+    let set: HashSet<_> = [b"a", b"b", b"a"].into_iter().map(chksum_sha2_224::hash).collect();
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn ordering() {
+    let lower = Digest::new([0x00; 28]);
+    let middle = chksum_sha2_224::hash(b"");
+    let upper = Digest::new([0xFF; 28]);
+    assert!(lower < middle);
+    assert!(middle < upper);
+    assert_eq!(middle.cmp(&middle), Ordering::Equal);
+
+    let mut map = BTreeMap::new();
+    map.insert(upper, "upper");
+    map.insert(lower, "lower");
+    map.insert(middle, "middle");
+    assert_eq!(map.into_values().collect::<Vec<_>>(), ["lower", "middle", "upper"]);
+}
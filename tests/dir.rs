@@ -0,0 +1,656 @@
+use std::io::Error as IoError;
+use std::path::Path;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_sha2_224::dir::{self, DirHasher, SymlinkPolicy};
+use chksum_sha2_224::{chksum, hash, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+const THREADS: [usize; 4] = [1, 2, 3, 16];
+
+fn hashers(path: &Path) -> impl Iterator<Item = DirHasher<'static>> + '_ {
+    THREADS
+        .into_iter()
+        .map(move |threads| DirHasher::new(path).threads(threads))
+}
+
+#[test]
+fn empty_directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+
+    for hasher in hashers(temp_dir.path()) {
+        let digest = hasher.chksum()?.to_hex_lowercase();
+        assert_eq!(digest, "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f");
+    }
+    assert_eq!(dir::chksum(temp_dir.path())?, chksum(temp_dir.path())?);
+
+    Ok(())
+}
+
+#[test]
+fn non_empty_directory_with_empty_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("file.txt").touch()?;
+
+    for hasher in hashers(temp_dir.path()) {
+        let digest = hasher.chksum()?.to_hex_lowercase();
+        assert_eq!(digest, "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn non_empty_directory_with_non_empty_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("file.txt").write_binary(b"data")?;
+
+    for hasher in hashers(temp_dir.path()) {
+        let digest = hasher.chksum()?.to_hex_lowercase();
+        assert_eq!(digest, "f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn non_empty_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(b"data")?;
+
+    for hasher in hashers(file.path()) {
+        let digest = hasher.chksum()?.to_hex_lowercase();
+        assert_eq!(digest, "f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn nested_directories_with_many_files() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    for index in 0..5 {
+        let directory = temp_dir.child(format!("dir-{index}"));
+        directory.create_dir_all()?;
+        directory.child("nested").create_dir_all()?;
+        for file in 0..20 {
+            let data = format!("{index} {file}").repeat(file * 700);
+            directory
+                .child(format!("file-{file}.txt"))
+                .write_binary(data.as_bytes())?;
+            directory
+                .child("nested")
+                .child(format!("{file}"))
+                .write_binary(&data.as_bytes()[..file])?;
+        }
+    }
+    temp_dir.child("dir-2").child("empty").create_dir_all()?;
+    temp_dir.child("a.txt").write_binary(b"first")?;
+    temp_dir.child("z.txt").write_binary(b"last")?;
+
+    let expected = chksum(temp_dir.path())?;
+    for hasher in hashers(temp_dir.path()) {
+        assert_eq!(hasher.chksum()?, expected);
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinks_are_followed() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let target = temp_dir.child("target");
+    target.create_dir_all()?;
+    target.child("file.txt").write_binary(b"data")?;
+    let root = temp_dir.child("root");
+    root.create_dir_all()?;
+    root.child("file.txt").write_binary(b"other data")?;
+    std::os::unix::fs::symlink(target.path(), root.child("link").path())?;
+
+    let expected = chksum(root.path())?;
+    for hasher in hashers(root.path()) {
+        assert_eq!(hasher.chksum()?, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn missing_path() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let missing = temp_dir.child("missing");
+
+    for hasher in hashers(missing.path()) {
+        let error = hasher.chksum().unwrap_err();
+        assert!(matches!(error, ChksumError::Io(_)));
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn broken_symlink() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"data")?;
+    std::os::unix::fs::symlink(temp_dir.child("missing").path(), temp_dir.child("b").path())?;
+    temp_dir.child("c.txt").write_binary(b"data")?;
+
+    assert!(chksum(temp_dir.path()).is_err());
+    for hasher in hashers(temp_dir.path()) {
+        assert!(hasher.chksum().is_err());
+    }
+
+    Ok(())
+}
+
+fn project() -> Result<TempDir, Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child(".git").child("HEAD").write_binary(b"git ")?;
+    temp_dir.child(".hidden").write_binary(b"hidden ")?;
+    temp_dir.child("build.log").write_binary(b"log ")?;
+    temp_dir.child("src").child("lib.rs").write_binary(b"lib ")?;
+    temp_dir.child("src").child("main.log").write_binary(b"main log ")?;
+    temp_dir
+        .child("src")
+        .child("nested")
+        .child("mod.rs")
+        .write_binary(b"mod ")?;
+    temp_dir.child("target").child("out").write_binary(b"out ")?;
+    Ok(temp_dir)
+}
+
+#[test]
+fn default_options() -> Result<(), Error> {
+    let temp_dir = project()?;
+
+    let expected = hash(b"git hidden log lib main log mod out ");
+    assert_eq!(chksum(temp_dir.path())?, expected);
+    for hasher in hashers(temp_dir.path()) {
+        assert_eq!(hasher.chksum()?, expected);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_options() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = project()?;
+
+        let hashers = [
+            DirHasher::new(temp_dir.path()),
+            DirHasher::new(temp_dir.path()).ignore(".*").ignore("/target/"),
+            DirHasher::new(temp_dir.path()).include_hidden(false).max_depth(1),
+            DirHasher::new(temp_dir.path()).metadata(true),
+        ];
+        for hasher in hashers {
+            assert_eq!(hasher.async_chksum().await?, hasher.chksum()?, "{hasher:?}");
+        }
+        assert_eq!(
+            DirHasher::new(temp_dir.path()).async_chksum().await?,
+            chksum_sha2_224::async_chksum(temp_dir.path()).await?
+        );
+
+        let error = DirHasher::new(temp_dir.child("missing").path())
+            .async_chksum()
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ChksumError::Io(_)));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn ignore_patterns() -> Result<(), Error> {
+    let temp_dir = project()?;
+
+    type Vector = (&'static [&'static str], &'static [u8]);
+    let vectors: [Vector; 10] = [
+        (&["*.log"], b"git hidden lib mod out "),
+        (&["/*.log"], b"git hidden lib main log mod out "),
+        (&["src/*.log"], b"git hidden log lib mod out "),
+        (&["*.log", "!main.log"], b"git hidden lib main log mod out "),
+        (&["target/", ".git/"], b"hidden log lib main log mod "),
+        (&["out/"], b"git hidden log lib main log mod out "),
+        (&["src/**/*.rs"], b"git hidden log main log out "),
+        (&["src/**"], b"git hidden log out "),
+        (&["**/mod.rs", "# comment", ""], b"git hidden log lib main log out "),
+        (&["[a-c]*", "?ib.rs"], b"git hidden main log mod out "),
+    ];
+    for (patterns, expected) in vectors {
+        for hasher in hashers(temp_dir.path()) {
+            let hasher = patterns.iter().fold(hasher, |hasher, pattern| hasher.ignore(pattern));
+            assert_eq!(hasher.chksum()?, hash(expected), "patterns {patterns:?}");
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn ignored_directory_cannot_be_included_again() -> Result<(), Error> {
+    let temp_dir = project()?;
+
+    let hasher = DirHasher::new(temp_dir.path()).ignore("src/").ignore("!src/lib.rs");
+    assert_eq!(hasher.chksum()?, hash(b"git hidden log out "));
+
+    Ok(())
+}
+
+#[test]
+fn ignored_contents_can_be_included_again() -> Result<(), Error> {
+    let temp_dir = project()?;
+
+    for hasher in hashers(temp_dir.path()) {
+        let hasher = hasher.ignore("src/**").ignore("!src/lib.rs");
+        assert_eq!(hasher.chksum()?, hash(b"git hidden log lib out "));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn exclude_hidden() -> Result<(), Error> {
+    let temp_dir = project()?;
+
+    for hasher in hashers(temp_dir.path()) {
+        let digest = hasher.include_hidden(false).chksum()?;
+        assert_eq!(digest, hash(b"log lib main log mod out "));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn max_depth() -> Result<(), Error> {
+    let temp_dir = project()?;
+
+    type Vector = (usize, &'static [u8]);
+    let vectors: [Vector; 4] = [
+        (0, b""),
+        (1, b"hidden log "),
+        (2, b"git hidden log lib main log out "),
+        (3, b"git hidden log lib main log mod out "),
+    ];
+    for (max_depth, expected) in vectors {
+        for hasher in hashers(temp_dir.path()) {
+            let digest = hasher.max_depth(max_depth).chksum()?;
+            assert_eq!(digest, hash(expected), "max depth {max_depth}");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn project_with_symlink() -> Result<TempDir, Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"first ")?;
+    temp_dir.child("c.txt").write_binary(b"last")?;
+    let target = temp_dir.child("target");
+    target.create_dir_all()?;
+    target.child("file.txt").write_binary(b"target ")?;
+    std::os::unix::fs::symlink("target", temp_dir.child("b").path())?;
+    Ok(temp_dir)
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_policies() -> Result<(), Error> {
+    let temp_dir = project_with_symlink()?;
+
+    type Vector = (SymlinkPolicy, &'static [u8]);
+    let vectors: [Vector; 3] = [
+        (SymlinkPolicy::Follow, b"first target last"),
+        (SymlinkPolicy::HashTarget, b"first targetlast"),
+        (SymlinkPolicy::Skip, b"first last"),
+    ];
+    for (policy, expected) in vectors {
+        for hasher in hashers(temp_dir.path()) {
+            let hasher = hasher.ignore("/target/").symlinks(policy);
+            assert_eq!(hasher.chksum()?, hash(expected), "policy {policy:?}");
+        }
+    }
+
+    let hasher = DirHasher::new(temp_dir.path()).ignore("/target/");
+    assert_eq!(
+        hasher.clone().follow_symlinks(true).chksum()?,
+        hash(b"first target last")
+    );
+    assert_eq!(hasher.follow_symlinks(false).chksum()?, hash(b"first targetlast"));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_policy_error() -> Result<(), Error> {
+    let temp_dir = project_with_symlink()?;
+
+    for hasher in hashers(temp_dir.path()) {
+        let error = hasher.symlinks(SymlinkPolicy::Error).chksum().unwrap_err();
+        assert!(matches!(error, ChksumError::Io(_)));
+        assert!(error.to_string().contains("symbolic link"));
+    }
+    let hasher = DirHasher::new(temp_dir.path())
+        .ignore("b")
+        .symlinks(SymlinkPolicy::Error);
+    assert!(hasher.chksum().is_ok());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn broken_symlink_is_not_followed() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"data")?;
+    std::os::unix::fs::symlink("missing", temp_dir.child("b").path())?;
+
+    let hasher = DirHasher::new(temp_dir.path()).symlinks(SymlinkPolicy::HashTarget);
+    assert_eq!(hasher.chksum()?, hash(b"datamissing"));
+    let hasher = DirHasher::new(temp_dir.path()).symlinks(SymlinkPolicy::Skip);
+    assert_eq!(hasher.chksum()?, hash(b"data"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "number of threads must be greater than zero")]
+fn zero_threads() {
+    let _ = DirHasher::new(".").threads(0);
+}
+
+#[test]
+fn metadata_mode_detects_renames() -> Result<(), Error> {
+    let first = TempDir::new()?;
+    first.child("a.txt").write_binary(b"data")?;
+    let second = TempDir::new()?;
+    second.child("b.txt").write_binary(b"data")?;
+
+    assert_eq!(chksum(first.path())?, chksum(second.path())?);
+    for (first, second) in hashers(first.path()).zip(hashers(second.path())) {
+        assert_ne!(first.metadata(true).chksum()?, second.metadata(true).chksum()?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn metadata_mode_detects_empty_entries() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let before = DirHasher::new(temp_dir.path()).metadata(true).chksum()?;
+    temp_dir.child("empty").create_dir_all()?;
+    temp_dir.child("empty.txt").touch()?;
+    let after = DirHasher::new(temp_dir.path()).metadata(true).chksum()?;
+
+    assert_ne!(before, after);
+    assert_eq!(chksum(temp_dir.path())?, hash(b""));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn metadata_mode_detects_permissions() -> Result<(), Error> {
+    use std::fs::{self, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("script.sh");
+    file.write_binary(b"#!/bin/sh")?;
+    fs::set_permissions(file.path(), Permissions::from_mode(0o644))?;
+    let before = DirHasher::new(temp_dir.path()).metadata(true).chksum()?;
+    let content = chksum(temp_dir.path())?;
+    fs::set_permissions(file.path(), Permissions::from_mode(0o755))?;
+
+    assert_ne!(DirHasher::new(temp_dir.path()).metadata(true).chksum()?, before);
+    assert_eq!(chksum(temp_dir.path())?, content);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn metadata_mode_encoding() -> Result<(), Error> {
+    use std::fs;
+    use std::os::unix::fs::MetadataExt;
+
+    fn record(kind: u8, path: &[u8], metadata: &fs::Metadata) -> Vec<u8> {
+        let mut record = vec![kind];
+        record.extend_from_slice(&(path.len() as u64).to_be_bytes());
+        record.extend_from_slice(path);
+        record.extend_from_slice(&(metadata.mode() & 0o7777).to_be_bytes());
+        record.extend_from_slice(&metadata.mtime().to_be_bytes());
+        record.extend_from_slice(&(metadata.mtime_nsec() as u32).to_be_bytes());
+        record.extend_from_slice(&metadata.uid().to_be_bytes());
+        record.extend_from_slice(&metadata.gid().to_be_bytes());
+        record
+    }
+
+    let temp_dir = TempDir::new()?;
+    let directory = temp_dir.child("dir");
+    directory.create_dir_all()?;
+    let file = directory.child("file.txt");
+    file.write_binary(b"data")?;
+    std::os::unix::fs::symlink("dir/file.txt", temp_dir.child("link").path())?;
+
+    let mut expected = b"SHA224DIR\x01\x03".to_vec();
+    expected.extend(record(b'd', b"", &fs::metadata(temp_dir.path())?));
+    expected.extend(record(b'd', b"dir", &fs::metadata(directory.path())?));
+    expected.extend(record(b'f', b"dir/file.txt", &fs::metadata(file.path())?));
+    expected.extend_from_slice(hash(b"data").as_bytes());
+    expected.extend(record(
+        b'l',
+        b"link",
+        &fs::symlink_metadata(temp_dir.child("link").path())?,
+    ));
+    expected.extend_from_slice(&12u64.to_be_bytes());
+    expected.extend_from_slice(b"dir/file.txt");
+
+    for hasher in hashers(temp_dir.path()) {
+        let digest = hasher
+            .metadata(true)
+            .mtimes(true)
+            .ownership(true)
+            .follow_symlinks(false)
+            .chksum()?;
+        assert_eq!(digest, hash(&expected));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn metadata_options_without_metadata_mode() -> Result<(), Error> {
+    let temp_dir = project()?;
+
+    let hasher = DirHasher::new(temp_dir.path()).mtimes(true).ownership(true);
+    assert_eq!(hasher.chksum()?, chksum(temp_dir.path())?);
+
+    Ok(())
+}
+
+#[test]
+fn manifest() -> Result<(), Error> {
+    let temp_dir = project()?;
+
+    type Vector = (&'static str, &'static [u8]);
+    let vectors: [Vector; 7] = [
+        (".git/HEAD", b"git "),
+        (".hidden", b"hidden "),
+        ("build.log", b"log "),
+        ("src/lib.rs", b"lib "),
+        ("src/main.log", b"main log "),
+        ("src/nested/mod.rs", b"mod "),
+        ("target/out", b"out "),
+    ];
+    for hasher in hashers(temp_dir.path()) {
+        let manifest = hasher.manifest()?;
+        assert_eq!(manifest.digest(), chksum(temp_dir.path())?);
+        assert_eq!(manifest.entries().len(), vectors.len());
+        for (entry, (path, data)) in manifest.entries().iter().zip(vectors) {
+            assert_eq!(entry.path, Path::new(path));
+            assert_eq!(entry.digest, hash(data));
+            assert_eq!(entry.size, data.len() as u64);
+        }
+    }
+
+    let manifest = DirHasher::new(temp_dir.path())
+        .metadata(true)
+        .ignore("src/")
+        .manifest()?;
+    assert_eq!(manifest.entries().len(), 4);
+    assert_eq!(manifest.get("target/out").map(|entry| entry.size), Some(4));
+    assert!(manifest.get("src/lib.rs").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn manifest_of_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(b"data")?;
+
+    let manifest = DirHasher::new(file.path()).manifest()?;
+    assert_eq!(manifest.digest(), hash(b"data"));
+    assert_eq!(manifest.entries().len(), 1);
+    assert_eq!(manifest.entries()[0].path, Path::new(""));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn manifest_with_symlinks() -> Result<(), Error> {
+    let temp_dir = project_with_symlink()?;
+
+    let manifest = DirHasher::new(temp_dir.path())
+        .ignore("/target/")
+        .follow_symlinks(false)
+        .manifest()?;
+    let link = manifest.get("b").expect("link must be listed");
+    assert_eq!(link.digest, hash(b"target"));
+    assert_eq!(link.size, 6);
+
+    let manifest = DirHasher::new(temp_dir.path()).ignore("/target/").manifest()?;
+    let file = manifest.get("b/file.txt").expect("file must be listed");
+    assert_eq!(file.digest, hash(b"target "));
+
+    Ok(())
+}
+
+#[test]
+fn manifest_diff() -> Result<(), Error> {
+    let temp_dir = project()?;
+    let old = DirHasher::new(temp_dir.path()).manifest()?;
+    assert!(dir::diff(&old, &old).is_empty());
+
+    temp_dir.child("build.log").write_binary(b"new log")?;
+    temp_dir.child("src").child("new.rs").write_binary(b"new")?;
+    temp_dir.child("a.txt").touch()?;
+    std::fs::remove_file(temp_dir.child("target").child("out").path())?;
+    let new = DirHasher::new(temp_dir.path()).manifest()?;
+
+    let diff = dir::diff(&old, &new);
+    let added: Vec<_> = diff.added.iter().map(|entry| entry.path.as_path()).collect();
+    assert_eq!(added, [Path::new("a.txt"), Path::new("src/new.rs")]);
+    let removed: Vec<_> = diff.removed.iter().map(|entry| entry.path.as_path()).collect();
+    assert_eq!(removed, [Path::new("target/out")]);
+    assert_eq!(diff.modified.len(), 1);
+    let (before, after) = &diff.modified[0];
+    assert_eq!(before.path, Path::new("build.log"));
+    assert_eq!(before.digest, hash(b"log "));
+    assert_eq!(after.digest, hash(b"new log"));
+    assert_eq!(after.size, 7);
+
+    let reverse = dir::diff(&new, &old);
+    assert_eq!(reverse.added, diff.removed);
+    assert_eq!(reverse.removed, diff.added);
+
+    Ok(())
+}
+
+#[test]
+fn progress() -> Result<(), Error> {
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    let temp_dir = project()?;
+    let reports = Arc::new(Mutex::new(Vec::new()));
+
+    let hasher = DirHasher::new(temp_dir.path()).ignore(".*").ignore("/target/");
+    let collected = Arc::clone(&reports);
+    let hasher = hasher.progress(move |progress| {
+        let path = progress.path().map(Path::to_path_buf);
+        let report = (progress.processed(), progress.total(), path);
+        collected.lock().expect("lock must not be poisoned").push(report);
+    });
+    assert_eq!(hasher.chksum()?, hash(b"log lib main log mod "));
+
+    let total = Some(21);
+    let path = |relative: &str| Some(temp_dir.path().join(relative));
+    let expected: Vec<(u64, Option<u64>, Option<PathBuf>)> = vec![
+        (0, total, path("build.log")),
+        (4, total, path("build.log")),
+        (4, total, path("src/lib.rs")),
+        (8, total, path("src/lib.rs")),
+        (8, total, path("src/main.log")),
+        (17, total, path("src/main.log")),
+        (17, total, path("src/nested/mod.rs")),
+        (21, total, path("src/nested/mod.rs")),
+    ];
+    assert_eq!(*reports.lock().expect("lock must not be poisoned"), expected);
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_progress() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = project()?;
+        let report = |progress: &chksum_sha2_224::Progress<'_>| {
+            let path = progress.path().map(Path::to_path_buf);
+            (progress.processed(), progress.total(), path)
+        };
+
+        let mut expected = Vec::new();
+        let hasher = DirHasher::new(temp_dir.path()).progress(|progress| expected.push(report(progress)));
+        let digest = hasher.chksum()?;
+        drop(hasher);
+
+        let mut reports = Vec::new();
+        let hasher = DirHasher::new(temp_dir.path()).progress(|progress| reports.push(report(progress)));
+        assert_eq!(hasher.async_chksum().await?, digest);
+        drop(hasher);
+
+        assert_eq!(reports, expected);
+        assert_eq!(reports.len(), 14);
+        assert_eq!(
+            reports.last(),
+            Some(&(36, Some(36), Some(temp_dir.path().join("target/out"))))
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use chksum_sha2_224::{Digest, FormatError, Multibase};
+
+const MULTIBASES: [Multibase; 10] = [
+    Multibase::Base16,
+    Multibase::Base16Upper,
+    Multibase::Base32,
+    Multibase::Base32Upper,
+    Multibase::Base32Pad,
+    Multibase::Base32PadUpper,
+    Multibase::Base64,
+    Multibase::Base64Pad,
+    Multibase::Base64Url,
+    Multibase::Base64UrlPad,
+];
+
+fn digests() -> Vec<Digest> {
+    let data: Vec<u8> = (0..=255).collect();
+    vec![
+        chksum_sha2_224::hash(b""),
+        chksum_sha2_224::hash(b"data"),
+        chksum_sha2_224::hash(data),
+        Digest::new([0x00; 28]),
+        Digest::new([0xFF; 28]),
+    ]
+}
+
+#[test]
+fn base64() {
+    let digest = chksum_sha2_224::hash(b"data");
+    assert_eq!(digest.to_base64(), "9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ==");
+    assert_eq!(
+        Digest::try_from_base64("9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ==").unwrap(),
+        digest
+    );
+
+    let digest = chksum_sha2_224::hash([0, 1, 2, 3]);
+    assert_eq!(digest.to_base64(), "1w2gcF6uQqXFltkvMx3aJCG04U+LMDX7c7i3AA==");
+}
+
+#[test]
+fn base64_url() {
+    let digest = chksum_sha2_224::hash([0, 1, 2, 3]);
+    assert_eq!(digest.to_base64_url(), "1w2gcF6uQqXFltkvMx3aJCG04U-LMDX7c7i3AA");
+    assert_eq!(
+        Digest::try_from_base64_url("1w2gcF6uQqXFltkvMx3aJCG04U-LMDX7c7i3AA").unwrap(),
+        digest
+    );
+}
+
+#[test]
+fn base32() {
+    let digest = chksum_sha2_224::hash(b"data");
+    assert_eq!(digest.to_base32(), "6RZZM45MYA6EEQ2DWRJHQ7XCHXLCTGNIVHYU6QSQTFLWS===");
+    assert_eq!(
+        Digest::try_from_base32("6RZZM45MYA6EEQ2DWRJHQ7XCHXLCTGNIVHYU6QSQTFLWS===").unwrap(),
+        digest
+    );
+    assert_eq!(
+        Digest::try_from_base32("6rzzm45mya6eeq2dwrjhq7xchxlctgnivhyu6qsqtflws===").unwrap(),
+        digest
+    );
+}
+
+#[test]
+fn round_trip() {
+    for digest in digests() {
+        assert_eq!(Digest::try_from_base64(&digest.to_base64()).unwrap(), digest);
+        assert_eq!(Digest::try_from_base64_url(&digest.to_base64_url()).unwrap(), digest);
+        assert_eq!(Digest::try_from_base32(&digest.to_base32()).unwrap(), digest);
+        for base in MULTIBASES {
+            let encoded = digest.to_multibase(base);
+            assert!(encoded.starts_with(base.prefix()));
+            assert_eq!(Digest::try_from_multibase(&encoded).unwrap(), digest);
+        }
+    }
+}
+
+#[test]
+fn multibase() {
+    let digest = chksum_sha2_224::hash(b"data");
+    assert_eq!(
+        digest.to_multibase(Multibase::Base16),
+        "ff4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769"
+    );
+    assert_eq!(
+        digest.to_multibase(Multibase::Base32),
+        "b6rzzm45mya6eeq2dwrjhq7xchxlctgnivhyu6qsqtflws"
+    );
+    assert_eq!(
+        digest.to_multibase(Multibase::Base32PadUpper),
+        "C6RZZM45MYA6EEQ2DWRJHQ7XCHXLCTGNIVHYU6QSQTFLWS==="
+    );
+    assert_eq!(
+        digest.to_multibase(Multibase::Base64),
+        "m9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ"
+    );
+    assert_eq!(
+        digest.to_multibase(Multibase::Base64UrlPad),
+        "U9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ=="
+    );
+}
+
+#[test]
+fn multibase_prefix() {
+    for base in MULTIBASES {
+        assert_eq!(Multibase::from_prefix(base.prefix()), Some(base));
+    }
+    assert_eq!(Multibase::from_prefix('z'), None);
+}
+
+#[test]
+fn invalid_length() {
+    assert_eq!(
+        Digest::try_from_base64("9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ").err(),
+        Some(FormatError::InvalidLength { value: 38, proper: 40 })
+    );
+    assert_eq!(
+        Digest::try_from_base64_url("9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ==").err(),
+        Some(FormatError::InvalidLength { value: 40, proper: 38 })
+    );
+    assert_eq!(
+        Digest::try_from_base32("").err(),
+        Some(FormatError::InvalidLength { value: 0, proper: 48 })
+    );
+    assert_eq!(
+        Digest::try_from_multibase("m9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ==").err(),
+        Some(FormatError::InvalidLength { value: 41, proper: 39 })
+    );
+}
+
+#[test]
+fn invalid_character() {
+    assert_eq!(
+        Digest::try_from_base64("9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXa-==").err(),
+        Some(FormatError::InvalidCharacter {
+            character: '-',
+            index: 37
+        })
+    );
+    assert_eq!(
+        Digest::try_from_base64_url("9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXa/").err(),
+        Some(FormatError::InvalidCharacter {
+            character: '/',
+            index: 37
+        })
+    );
+    assert_eq!(
+        Digest::try_from_base64("9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ=A").err(),
+        Some(FormatError::InvalidCharacter {
+            character: 'A',
+            index: 39
+        })
+    );
+    assert_eq!(
+        Digest::try_from_base32("6RZZM45MYA6EEQ2DWRJHQ7XCHXLCTGNIVHYU6QSQTFLW1===").err(),
+        Some(FormatError::InvalidCharacter {
+            character: '1',
+            index: 44
+        })
+    );
+    assert_eq!(
+        Digest::try_from_multibase("ff4739673acc03c424343b452787ee23dd62999a8a9f14f425099576x").err(),
+        Some(FormatError::InvalidCharacter {
+            character: 'x',
+            index: 56
+        })
+    );
+}
+
+#[test]
+fn non_canonical_trailing_bits() {
+    // the last symbol encodes only two bits of the digest, the rest must be zero
+    assert_eq!(
+        Digest::try_from_base64("9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaR==").err(),
+        Some(FormatError::InvalidCharacter {
+            character: 'R',
+            index: 37
+        })
+    );
+    // the last symbol encodes only one bit of the digest, the rest must be zero
+    assert_eq!(
+        Digest::try_from_base32("6RZZM45MYA6EEQ2DWRJHQ7XCHXLCTGNIVHYU6QSQTFLWT===").err(),
+        Some(FormatError::InvalidCharacter {
+            character: 'T',
+            index: 44
+        })
+    );
+}
+
+#[test]
+fn unknown_multibase_prefix() {
+    assert_eq!(
+        Digest::try_from_multibase("").err(),
+        Some(FormatError::UnknownBase { prefix: None })
+    );
+    assert_eq!(
+        Digest::try_from_multibase("zABC").err(),
+        Some(FormatError::UnknownBase { prefix: Some('z') })
+    );
+}
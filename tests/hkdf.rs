@@ -0,0 +1,115 @@
+use chksum_sha2_224::hkdf::{self, Error, MAX_OUTPUT_LENGTH};
+
+// salt, input keying material, info, length, pseudorandom key and output keying material
+type Vector = (
+    &'static [u8],
+    &'static [u8],
+    &'static [u8],
+    usize,
+    &'static str,
+    &'static str,
+);
+
+// known-answer vectors generated for SHA-224 with the inputs of RFC 5869, appendix A, test cases 1 to 3
+const VECTORS: [Vector; 3] = [
+    (
+        &[0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C],
+        &[0x0B; 22],
+        &[0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9],
+        42,
+        "94f65bed12265c1fa2747db60cadfcabbbbaede6be5a7a450de78231",
+        "2f21cd7cbc818ca5c561b933728e2e08e154a87e1432399a820dee13aa222d0cee6152fa539ab70f8e80",
+    ),
+    (
+        &[
+            0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71,
+            0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0x7B, 0x7C, 0x7D, 0x7E, 0x7F, 0x80, 0x81, 0x82, 0x83,
+            0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x8D, 0x8E, 0x8F, 0x90, 0x91, 0x92, 0x93, 0x94, 0x95,
+            0x96, 0x97, 0x98, 0x99, 0x9A, 0x9B, 0x9C, 0x9D, 0x9E, 0x9F, 0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0xA7,
+            0xA8, 0xA9, 0xAA, 0xAB, 0xAC, 0xAD, 0xAE, 0xAF,
+        ],
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x10, 0x11,
+            0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F, 0x20, 0x21, 0x22, 0x23,
+            0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2A, 0x2B, 0x2C, 0x2D, 0x2E, 0x2F, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35,
+            0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F, 0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+            0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F,
+        ],
+        &[
+            0xB0, 0xB1, 0xB2, 0xB3, 0xB4, 0xB5, 0xB6, 0xB7, 0xB8, 0xB9, 0xBA, 0xBB, 0xBC, 0xBD, 0xBE, 0xBF, 0xC0, 0xC1,
+            0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE, 0xCF, 0xD0, 0xD1, 0xD2, 0xD3,
+            0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9, 0xDA, 0xDB, 0xDC, 0xDD, 0xDE, 0xDF, 0xE0, 0xE1, 0xE2, 0xE3, 0xE4, 0xE5,
+            0xE6, 0xE7, 0xE8, 0xE9, 0xEA, 0xEB, 0xEC, 0xED, 0xEE, 0xEF, 0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7,
+            0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF,
+        ],
+        82,
+        "2cde1de74eb0175dc3d177ab19c9fc95ace3305ddde43bb4908161c7",
+        "3e49703c243a3894916349b52a8f55c7c160452f97b2870f04ba924ba9056ab351765b04207231158dcb03d0c7d427cb2b7e060179459f9daffee05e8705113f7bc45b4f452601d884df6dfd4ff9dacfde69",
+    ),
+    (
+        &[],
+        &[0x0B; 22],
+        &[],
+        42,
+        "31dede67ca3dc33d6fbf58addb7812ac65f2bec66ee41b157b5db338",
+        "2a268083ea787e06604a5845f1a53544dd7847bd6fb74adfcc1178baac5a0fe74076f8935971c00c2b19",
+    ),
+];
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[test]
+fn known_answer_vectors() {
+    for (salt, ikm, info, length, prk, okm) in VECTORS {
+        let extracted = hkdf::extract(salt, ikm);
+        assert_eq!(extracted, prk);
+
+        let expanded = hkdf::expand(extracted, info, length).unwrap();
+        assert_eq!(expanded.len(), length);
+        assert_eq!(to_hex(&expanded), okm);
+
+        let derived = hkdf::derive(salt, ikm, info, length).unwrap();
+        assert_eq!(derived, expanded);
+    }
+}
+
+#[test]
+fn empty_salt_equals_zero_salt() {
+    assert_eq!(hkdf::extract([], b"secret"), hkdf::extract([0; 28], b"secret"));
+}
+
+#[test]
+fn expand_prefixes() {
+    let prk = hkdf::extract(b"salt", b"secret");
+    let okm = hkdf::expand(prk, b"info", 100).unwrap();
+    for length in [0, 1, 27, 28, 29, 56, 99] {
+        assert_eq!(hkdf::expand(prk, b"info", length).unwrap(), okm[..length]);
+    }
+}
+
+#[test]
+fn expand_maximum_length() {
+    let prk = hkdf::extract(b"salt", b"secret");
+    let okm = hkdf::expand(prk, b"info", MAX_OUTPUT_LENGTH).unwrap();
+    assert_eq!(okm.len(), 255 * 28);
+    assert_eq!(
+        to_hex(&okm[MAX_OUTPUT_LENGTH - 28..]),
+        "2a32821d8ff6359e0904311f001989e3ab9eba2c68dfe2c62ce49a9c"
+    );
+
+    assert_eq!(
+        hkdf::expand(prk, b"info", MAX_OUTPUT_LENGTH + 1),
+        Err(Error::InvalidLength {
+            value: 7141,
+            maximum: 7140,
+        })
+    );
+    assert_eq!(
+        hkdf::derive(b"salt", b"secret", b"info", usize::MAX)
+            .unwrap_err()
+            .to_string(),
+        format!("Invalid output length `{}`, maximum value `7140`", usize::MAX)
+    );
+}
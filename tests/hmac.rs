@@ -0,0 +1,136 @@
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_224::hmac::{self, HmacSha224};
+use chksum_sha2_224::{Digest, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+}
+
+const DATA_TAG: &str = "19424d4210e50d7a4521b5f0d54b4b0cff3060deddccfd894fda5b3b";
+
+// test cases from RFC 4231, section 4
+const RFC_4231: [(&[u8], &[u8], &str); 6] = [
+    (
+        &[0x0B; 20],
+        b"Hi There",
+        "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+    ),
+    (
+        b"Jefe",
+        b"what do ya want for nothing?",
+        "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+    ),
+    (
+        &[0xAA; 20],
+        &[0xDD; 50],
+        "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea",
+    ),
+    (
+        &[
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x10, 0x11, 0x12,
+            0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19,
+        ],
+        &[0xCD; 50],
+        "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a",
+    ),
+    (
+        &[0xAA; 131],
+        b"Test Using Larger Than Block-Size Key - Hash Key First",
+        "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+    ),
+    (
+        &[0xAA; 131],
+        b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be \
+          hashed before being used by the HMAC algorithm.",
+        "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1",
+    ),
+];
+
+#[test]
+fn rfc_4231_test_vectors() {
+    for (key, data, expected) in RFC_4231 {
+        assert_eq!(hmac::hmac(key, data), expected);
+
+        let mut hmac = HmacSha224::new(key);
+        for chunk in data.chunks(7) {
+            hmac.update(chunk);
+        }
+        assert_eq!(hmac.finalize(), expected);
+    }
+}
+
+#[test]
+fn rfc_4231_truncated_output() {
+    let tag = hmac::hmac([0x0C; 20], b"Test With Truncation");
+    assert_eq!(
+        tag.as_bytes()[..16],
+        [0x0E, 0x2A, 0xEA, 0x68, 0xA9, 0x0C, 0x8D, 0x37, 0xC9, 0x88, 0xBC, 0xDB, 0x9F, 0xCA, 0x6F, 0xA8]
+    );
+}
+
+#[test]
+fn reset_keeps_key() {
+    let mut hmac = HmacSha224::new(b"key");
+    hmac.update(b"other data");
+    hmac.reset();
+    hmac.update(b"data");
+    assert_eq!(hmac.finalize(), DATA_TAG);
+
+    // finalization doesn't consume the state
+    hmac.update(b"");
+    assert_eq!(hmac.finalize(), DATA_TAG);
+}
+
+#[test]
+fn verify_tag() {
+    let expected = Digest::try_from_hex(DATA_TAG).unwrap();
+    let mut hmac = HmacSha224::new(b"key");
+    hmac.update(b"data");
+    assert!(hmac.verify(&expected));
+    assert!(hmac::verify(b"key", b"data", &expected));
+    assert!(!hmac::verify(b"key", b"other data", &expected));
+    assert!(!hmac::verify(b"other key", b"data", &expected));
+}
+
+#[test]
+fn debug_hides_state() {
+    let hmac = HmacSha224::new(b"key");
+    assert_eq!(format!("{hmac:?}"), "HmacSha224 { .. }");
+}
+
+#[test]
+fn chksum_file_and_directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(b"data")?;
+
+    assert_eq!(hmac::chksum(b"key", file.path())?, DATA_TAG);
+    assert_eq!(hmac::chksum(b"key", temp_dir.path())?, DATA_TAG);
+
+    let expected = Digest::try_from_hex(DATA_TAG).unwrap();
+    assert!(hmac::chksum_verify(b"key", file.path(), &expected)?);
+    assert!(!hmac::chksum_verify(b"other key", file.path(), &expected)?);
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_chksum_file() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"data")?;
+
+        assert_eq!(hmac::async_chksum(b"key", file.path()).await?, DATA_TAG);
+    }
+
+    Ok(())
+}
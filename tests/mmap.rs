@@ -0,0 +1,211 @@
+use std::sync::{Arc, Mutex};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_224::dir::DirHasher;
+use chksum_sha2_224::mmap::{self, THRESHOLD};
+use chksum_sha2_224::tree::{self, TreeHasher};
+use chksum_sha2_224::{
+    chksum,
+    chksum_file_with,
+    chksum_mmap,
+    hash,
+    CancellationToken,
+    Cancelled,
+    ChksumOptions,
+    Error as ChksumError,
+};
+
+mod common;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+}
+
+fn large_data() -> Vec<u8> {
+    let length = usize::try_from(THRESHOLD).expect("threshold must fit in usize") + 12_345;
+    common::data(length)
+}
+
+#[test]
+fn empty_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.touch()?;
+
+    let digest = chksum_mmap(file.path())?.to_hex_lowercase();
+    assert_eq!(digest, "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f");
+
+    Ok(())
+}
+
+#[test]
+fn small_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(b"data")?;
+
+    let digest = mmap::chksum(file.path())?.to_hex_lowercase();
+    assert_eq!(digest, "f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769");
+
+    Ok(())
+}
+
+#[test]
+fn large_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    let data = large_data();
+    file.write_binary(&data)?;
+
+    let digest = chksum_mmap(file.path())?;
+    assert_eq!(digest, hash(&data));
+    assert_eq!(digest, chksum(file.path())?);
+
+    Ok(())
+}
+
+#[test]
+fn directory_with_large_files() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let data = large_data();
+    temp_dir.child("a.bin").write_binary(&data)?;
+    temp_dir.child("b.txt").write_binary(b"data")?;
+    temp_dir.child("c.bin").write_binary(&data[1..])?;
+
+    let expected = chksum(temp_dir.path())?;
+    for threads in [1, 2, 4] {
+        let hasher = DirHasher::new(temp_dir.path())
+            .threads(threads)
+            .mmap_threshold(Some(THRESHOLD));
+        assert_eq!(hasher.chksum()?, expected);
+        let manifest = hasher.manifest()?;
+        let entry = manifest.get("a.bin").expect("file must be listed");
+        assert_eq!(entry.digest, hash(&data));
+        assert_eq!(entry.size, data.len() as u64);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn directory_reports_progress_inside_mapped_files() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let data = large_data();
+    temp_dir.child("a.bin").write_binary(&data)?;
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let collected = Arc::clone(&reports);
+    let digest = DirHasher::new(temp_dir.path())
+        .mmap_threshold(Some(0))
+        .progress(move |progress| collected.lock().unwrap().push(progress.processed()))
+        .chksum()?;
+    assert_eq!(digest, chksum(temp_dir.path())?);
+
+    let reports = reports.lock().unwrap();
+    // the file is hashed in chunks of 64 KiB
+    assert_eq!(reports.len(), 1 + data.len().div_ceil(64 * 1024));
+    assert_eq!(reports.last().copied(), Some(data.len() as u64));
+
+    Ok(())
+}
+
+#[test]
+fn directory_cancelled_inside_mapped_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.bin").write_binary(&large_data())?;
+
+    let token = CancellationToken::new();
+    let cancel = token.clone();
+    let error = DirHasher::new(temp_dir.path())
+        .mmap_threshold(Some(0))
+        .cancellation(token)
+        .progress(move |progress| {
+            if progress.processed() > 0 {
+                cancel.cancel();
+            }
+        })
+        .chksum()
+        .unwrap_err();
+    assert!(Cancelled::matches(&error));
+
+    Ok(())
+}
+
+#[test]
+fn file_with_options() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    let data = large_data();
+    file.write_binary(&data)?;
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let collected = Arc::clone(&reports);
+    let mut options = ChksumOptions::new()
+        .buffer_size(64 * 1024)
+        .mmap_threshold(Some(THRESHOLD))
+        .progress(move |progress| collected.lock().unwrap().push(progress.processed()));
+    assert_eq!(chksum_file_with(file.path(), &mut options)?, hash(&data));
+
+    // the mapped file is hashed in chunks of the buffer size
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), data.len().div_ceil(64 * 1024));
+    assert_eq!(reports.last().copied(), Some(data.len() as u64));
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let mut options = ChksumOptions::new().mmap_threshold(Some(0)).cancellation(token);
+    let error = chksum_file_with(file.path(), &mut options).unwrap_err();
+    assert!(Cancelled::matches(&error));
+
+    Ok(())
+}
+
+#[test]
+fn tree_of_large_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    let data = large_data();
+    file.write_binary(&data)?;
+
+    let hasher = TreeHasher::new().leaf_size(64 * 1024);
+    assert_eq!(hasher.chksum(file.path())?, hasher.hash(&data));
+    let hasher = hasher.mmap_threshold(Some(THRESHOLD));
+    assert_eq!(hasher.chksum(file.path())?, hasher.hash(&data));
+    assert_eq!(tree::chksum(file.path())?, tree::hash(&data));
+
+    Ok(())
+}
+
+#[test]
+fn directory_falls_back_to_buffered_io() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+
+    assert!(chksum_mmap(temp_dir.path()).is_err());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn special_file_falls_back_to_buffered_io() -> Result<(), Error> {
+    let digest = chksum_mmap("/dev/null")?;
+    assert_eq!(digest, hash(b""));
+
+    Ok(())
+}
+
+#[test]
+fn missing_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+
+    let error = chksum_mmap(temp_dir.child("missing").path()).unwrap_err();
+    assert!(matches!(error, ChksumError::Io(_)));
+
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use std::fs::File;
+use std::io::{self, Error as IoError, ErrorKind, Read};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_224::{chksum, chksum_file_with, chksum_with, hash, ChksumOptions, Error as ChksumError};
+
+mod common;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// A reader which is interrupted before every read.
+struct Interrupted<'a> {
+    data: &'a [u8],
+    interrupted: bool,
+}
+
+impl Read for Interrupted<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        self.interrupted = !self.interrupted;
+        if self.interrupted {
+            return Err(ErrorKind::Interrupted.into());
+        }
+        let length = buffer.len().min(self.data.len());
+        buffer[..length].copy_from_slice(&self.data[..length]);
+        self.data = &self.data[length..];
+        Ok(length)
+    }
+}
+
+#[test]
+fn empty_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.touch()?;
+
+    let digest = chksum_with(File::open(file.path())?, &mut ChksumOptions::new())?.to_hex_lowercase();
+    assert_eq!(digest, "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f");
+
+    Ok(())
+}
+
+#[test]
+fn buffer_sizes() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    let data = common::data(100_000);
+    file.write_binary(&data)?;
+
+    let expected = chksum(file.path())?;
+    for size in [1, 7, 64, 8 * 1024, 1024 * 1024] {
+        let mut options = ChksumOptions::new().buffer_size(size);
+        assert_eq!(chksum_with(File::open(file.path())?, &mut options)?, expected);
+        assert_eq!(chksum_with(&data[..], &mut options)?, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn file_path() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    let data = common::data(100_000);
+    file.write_binary(&data)?;
+
+    let mut options = ChksumOptions::new().buffer_size(7);
+    assert_eq!(chksum_file_with(file.path(), &mut options)?, hash(&data));
+    assert!(chksum_file_with(temp_dir.child("missing").path(), &mut options).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn caller_provided_buffer() -> Result<(), Error> {
+    let first = common::data(100_000);
+    let second = b"data".to_vec();
+
+    let mut buffer = [0u8; 1000];
+    let mut options = ChksumOptions::new().buffer(&mut buffer);
+    assert_eq!(chksum_with(&first[..], &mut options)?, hash(&first));
+    assert_eq!(chksum_with(&second[..], &mut options)?, hash(&second));
+    drop(options);
+    assert_eq!(buffer[..4], second[..]);
+
+    Ok(())
+}
+
+#[test]
+fn interrupted_reader() -> Result<(), Error> {
+    let data = common::data(100_000);
+    let reader = Interrupted {
+        data: &data,
+        interrupted: false,
+    };
+
+    let mut options = ChksumOptions::new().buffer_size(4096);
+    assert_eq!(chksum_with(reader, &mut options)?, hash(&data));
+
+    Ok(())
+}
+
+#[test]
+fn progress() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    let data = common::data(100_000);
+    file.write_binary(&data)?;
+
+    let mut reports = Vec::new();
+    let file = File::open(file.path())?;
+    let total = file.metadata()?.len();
+    let mut options = ChksumOptions::new()
+        .buffer_size(40_000)
+        .total(total)
+        .progress(|progress| reports.push((progress.processed(), progress.total(), progress.path().is_some())));
+    assert_eq!(chksum_with(file, &mut options)?, hash(&data));
+    drop(options);
+
+    let total = Some(100_000);
+    assert_eq!(
+        reports,
+        [(40_000, total, false), (80_000, total, false), (100_000, total, false)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn progress_without_total() -> Result<(), Error> {
+    let mut reports = Vec::new();
+    let mut options = ChksumOptions::new()
+        .buffer_size(3)
+        .progress(|progress| reports.push((progress.processed(), progress.total())));
+    chksum_with(&b"data"[..], &mut options)?;
+    drop(options);
+
+    assert_eq!(reports, [(3, None), (4, None)]);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "buffer size must be greater than zero")]
+fn zero_buffer_size() {
+    let _ = ChksumOptions::new().buffer_size(0);
+}
+
+#[test]
+#[should_panic(expected = "buffer must not be empty")]
+fn empty_buffer() {
+    let _ = ChksumOptions::new().buffer(&mut []);
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_buffer_sizes() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use chksum_sha2_224::async_chksum_with;
+
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.bin");
+        let data = common::data(100_000);
+        file.write_binary(&data)?;
+
+        let mut buffer = vec![0u8; 3000];
+        for mut options in [
+            ChksumOptions::new(),
+            ChksumOptions::new().buffer_size(7),
+            ChksumOptions::new().buffer(&mut buffer),
+        ] {
+            let file = tokio::fs::File::open(file.path()).await?;
+            assert_eq!(async_chksum_with(file, &mut options).await?, hash(&data));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_progress() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        use chksum_sha2_224::async_chksum_with;
+
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.bin");
+        let data = common::data(100_000);
+        file.write_binary(&data)?;
+
+        let processed = AtomicU64::new(0);
+        let mut options = ChksumOptions::new()
+            .total(data.len() as u64)
+            .progress(|progress| processed.store(progress.processed(), Ordering::Relaxed));
+        let file = tokio::fs::File::open(file.path()).await?;
+        assert_eq!(async_chksum_with(file, &mut options).await?, hash(&data));
+        assert_eq!(processed.load(Ordering::Relaxed), data.len() as u64);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use chksum_sha2_224::pbkdf2::{self, Error, PasswordHash};
+use chksum_sha2_224::FormatError;
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+// password, salt, rounds, length and derived key
+type Vector = (&'static [u8], &'static [u8], u32, usize, &'static str);
+
+// known-answer vectors generated for SHA-224 with the inputs of RFC 6070
+const VECTORS: [Vector; 5] = [
+    (
+        b"password",
+        b"salt",
+        1,
+        28,
+        "3c198cbdb9464b7857966bd05b7bc92bc1cc4e6e63155d4e490557fd",
+    ),
+    (
+        b"password",
+        b"salt",
+        2,
+        28,
+        "93200ffa96c5776d38fa10abdf8f5bfc0054b9718513df472d2331d2",
+    ),
+    (
+        b"password",
+        b"salt",
+        4096,
+        28,
+        "218c453bf90635bd0a21a75d172703ff6108ef603f65bb821aedade1",
+    ),
+    (
+        b"passwordPASSWORDpassword",
+        b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+        4096,
+        40,
+        "056c4ba438ded91fc14e0594e6f52b87e1f3690c0dc0fbc05784ed9a754ca780e6c017e80c8de278",
+    ),
+    (b"pass\0word", b"sa\0lt", 4096, 16, "9b4011b641f40a2a500a31d4a392d15c"),
+];
+
+const PHC_29000: &str = "$pbkdf2-sha224$29000$AAECAwQFBgcICQoLDA0ODw$en3cSZ4ggdVvEWfC1LPBWJen4K93Z4wbUCdXtQ";
+const PHC_1000: &str = "$pbkdf2-sha224$1000$//77//77//77//77$zr/v5E0sgFbzNnQ87zOzmLa4.R4NO464PJS7jA";
+
+#[test]
+fn known_answer_vectors() {
+    for (password, salt, rounds, length, expected) in VECTORS {
+        let key = pbkdf2::derive(password, salt, rounds, length).unwrap();
+        assert_eq!(to_hex(&key), expected);
+    }
+}
+
+#[test]
+fn derive_prefixes() {
+    let key = pbkdf2::derive(b"password", b"salt", 2, 100).unwrap();
+    for length in [0, 1, 27, 28, 29, 56, 99] {
+        assert_eq!(pbkdf2::derive(b"password", b"salt", 2, length).unwrap(), key[..length]);
+    }
+}
+
+#[test]
+fn derive_invalid_rounds() {
+    assert_eq!(
+        pbkdf2::derive(b"password", b"salt", 0, 28),
+        Err(Error::InvalidRounds { value: 0 })
+    );
+    assert_eq!(
+        PasswordHash::new(b"password", b"salt", 0),
+        Err(Error::InvalidRounds { value: 0 })
+    );
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn derive_invalid_length() {
+    let length = (u32::MAX as usize) * 28 + 1;
+    assert_eq!(
+        pbkdf2::derive(b"password", b"salt", 1, length),
+        Err(Error::InvalidLength { value: length })
+    );
+}
+
+#[test]
+fn parse_and_format() {
+    let hash = PHC_29000.parse::<PasswordHash>().unwrap();
+    assert_eq!(hash.rounds, 29_000);
+    assert_eq!(hash.salt, (0..16).collect::<Vec<u8>>());
+    assert_eq!(hash.hash.len(), 28);
+    assert_eq!(hash.to_string(), PHC_29000);
+
+    let hash = PHC_1000.parse::<PasswordHash>().unwrap();
+    assert_eq!(hash.rounds, 1000);
+    assert_eq!(hash.salt, [0xFF, 0xFE, 0xFB].repeat(4));
+    assert_eq!(hash.to_string(), PHC_1000);
+}
+
+#[test]
+fn new_and_verify() {
+    let hash = PasswordHash::new(b"password", (0..16).collect::<Vec<u8>>(), 29_000).unwrap();
+    assert_eq!(hash.to_string(), PHC_29000);
+    assert!(hash.verify(b"password"));
+    assert!(!hash.verify(b"Password"));
+
+    assert!(pbkdf2::verify(b"password", PHC_1000).unwrap());
+    assert!(!pbkdf2::verify(b"password ", PHC_1000).unwrap());
+}
+
+#[test]
+fn verify_truncated_hash() {
+    // hashes shorter than the digest are verified against a derived key of the same length
+    let hash = "$pbkdf2-sha224$1$c2FsdA$PBmMvQ";
+    assert!(pbkdf2::verify(b"password", hash).unwrap());
+    assert!(!pbkdf2::verify(b"passw0rd", hash).unwrap());
+}
+
+#[test]
+fn parse_invalid() {
+    for hash in [
+        "",
+        "pbkdf2-sha224$1$c2FsdA$PBmMvQ",
+        "$pbkdf2-sha224$1$c2FsdA",
+        "$pbkdf2-sha224$1$c2FsdA$PBmMvQ$",
+        "$pbkdf2-sha224$$c2FsdA$PBmMvQ",
+        "$pbkdf2-sha224$0$c2FsdA$PBmMvQ",
+        "$pbkdf2-sha224$01$c2FsdA$PBmMvQ",
+        "$pbkdf2-sha224$+1$c2FsdA$PBmMvQ",
+        "$pbkdf2-sha224$4294967296$c2FsdA$PBmMvQ",
+        "$pbkdf2-sha224$1$c2FsdA$",
+    ] {
+        assert_eq!(hash.parse::<PasswordHash>(), Err(Error::InvalidFormat), "{hash}");
+    }
+
+    assert_eq!(
+        "$pbkdf2-sha256$1$c2FsdA$PBmMvQ".parse::<PasswordHash>(),
+        Err(Error::UnsupportedAlgorithm {
+            algorithm: "pbkdf2-sha256".to_owned()
+        })
+    );
+    assert_eq!(
+        "$pbkdf2-sha224$1$c2Fsd+$PBmMvQ".parse::<PasswordHash>(),
+        Err(Error::InvalidSalt(FormatError::InvalidCharacter {
+            character: '+',
+            index: 5
+        }))
+    );
+    assert_eq!(
+        "$pbkdf2-sha224$1$c2FsdA$PBmMv".parse::<PasswordHash>(),
+        Err(Error::InvalidHash(FormatError::InvalidCharacter {
+            character: 'v',
+            index: 4
+        }))
+    );
+    assert_eq!(
+        "$pbkdf2-sha224$1$c2FsdB$PBmMvQ".parse::<PasswordHash>(),
+        Err(Error::InvalidSalt(FormatError::InvalidCharacter {
+            character: 'B',
+            index: 5
+        }))
+    );
+    assert!(pbkdf2::verify(b"password", "$pbkdf2-sha224$1$c2FsdA$PBmMvQ==").is_err());
+}
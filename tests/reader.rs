@@ -0,0 +1,227 @@
+use std::fs::File;
+use std::io::{self, Error as IoError, ErrorKind, Read, Write};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_224::reader::{self, TeeError, VerifyingReader};
+use chksum_sha2_224::{hash, VerifyError};
+
+mod common;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Tee(#[from] TeeError),
+}
+
+/// A writer which fails after accepting the given number of bytes.
+struct Failing {
+    remaining: usize,
+}
+
+impl Write for Failing {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        if self.remaining == 0 {
+            return Err(IoError::other("sink is full"));
+        }
+        let length = buffer.len().min(self.remaining);
+        self.remaining -= length;
+        Ok(length)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A reader which fails after returning the given data.
+struct Broken<'a>(&'a [u8]);
+
+impl Read for Broken<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() {
+            return Err(ErrorKind::ConnectionReset.into());
+        }
+        self.0.read(buffer)
+    }
+}
+
+#[test]
+fn verifying_matching_data() -> Result<(), Error> {
+    let data = common::data(100_000);
+
+    let mut reader = reader::verifying(&data[..], hash(&data));
+    let mut output = Vec::new();
+    io::copy(&mut reader, &mut output)?;
+    assert_eq!(output, data);
+
+    Ok(())
+}
+
+#[test]
+fn verifying_empty_data() -> Result<(), Error> {
+    let mut reader = reader::verifying(io::empty(), hash(b""));
+    let mut output = Vec::new();
+    reader.read_to_end(&mut output)?;
+    assert!(output.is_empty());
+
+    let mut reader = reader::verifying(io::empty(), hash(b"data"));
+    assert!(reader.read_to_end(&mut output).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn verifying_mismatched_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    let mut data = common::data(100_000);
+    file.write_binary(&data)?;
+    let actual = hash(&data);
+    data[50_000] ^= 0x01;
+    let expected = hash(&data);
+
+    let mut reader = VerifyingReader::new(File::open(file.path())?, expected);
+    let error = io::copy(&mut reader, &mut io::sink()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    match VerifyError::from_io_error(&error) {
+        Some(VerifyError::Mismatch {
+            expected: mismatch_expected,
+            actual: mismatch_actual,
+        }) => {
+            assert_eq!(*mismatch_expected, expected);
+            assert_eq!(*mismatch_actual, actual);
+        },
+        other => panic!("unexpected error {other:?}"),
+    }
+
+    // the end of the data keeps failing
+    let mut buffer = [0u8; 16];
+    assert!(reader.read(&mut buffer).is_err());
+    assert_eq!(reader.read(&mut [])?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn verify_error_of_other_errors() {
+    assert!(VerifyError::from_io_error(&IoError::other("other")).is_none());
+    assert!(VerifyError::from_io_error(&ErrorKind::InvalidData.into()).is_none());
+}
+
+#[test]
+fn tee() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let path = temp_dir.child("file.bin");
+    let data = common::data(100_000);
+
+    let mut file = File::create(path.path())?;
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+    let output = reader::tee(&data[..], &mut [&mut file, &mut first, &mut second])?;
+    drop(file);
+
+    assert_eq!(output.digest, hash(&data));
+    assert_eq!(output.length, data.len() as u64);
+    assert_eq!(std::fs::read(path.path())?, data);
+    assert_eq!(first, data);
+    assert_eq!(second, data);
+
+    Ok(())
+}
+
+#[test]
+fn tee_without_sinks() -> Result<(), Error> {
+    let output = reader::tee(io::empty(), &mut [])?;
+    assert_eq!(output.digest, hash(b""));
+    assert_eq!(output.length, 0);
+
+    Ok(())
+}
+
+#[test]
+fn tee_failing_sink() {
+    let data = common::data(100_000);
+
+    let mut first = Vec::new();
+    let mut failing = Failing { remaining: 20_000 };
+    let mut last = Vec::new();
+    let error = reader::tee(&data[..], &mut [&mut first, &mut failing, &mut last]).unwrap_err();
+    let TeeError::Write { index, length, source } = error else {
+        panic!("unexpected error {error:?}");
+    };
+    assert_eq!(index, 1);
+    assert_eq!(length, 16_384);
+    assert_eq!(source.to_string(), "sink is full");
+    assert_eq!(first, data[..24_576]);
+    assert_eq!(last, data[..16_384]);
+}
+
+#[test]
+fn tee_failing_reader() {
+    let data = common::data(100_000);
+
+    let mut sink = Vec::new();
+    let error = reader::tee(Broken(&data[..10]), &mut [&mut sink]).unwrap_err();
+    assert!(matches!(&error, TeeError::Read(error) if error.kind() == ErrorKind::ConnectionReset));
+    assert_eq!(sink, data[..10]);
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_tee() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.child("file.bin");
+        let data = common::data(100_000);
+
+        let mut file = tokio::fs::File::create(path.path()).await?;
+        let mut sink = Vec::new();
+        let output = reader::async_tee(&data[..], &mut [&mut file, &mut sink]).await?;
+        drop(file);
+
+        assert_eq!(output.digest, hash(&data));
+        assert_eq!(output.length, data.len() as u64);
+        assert_eq!(tokio::fs::read(path.path()).await?, data);
+        assert_eq!(sink, data);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_verifying() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use tokio::io::{self as async_io, AsyncReadExt};
+
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.bin");
+        let data = common::data(100_000);
+        file.write_binary(&data)?;
+
+        let inner = tokio::fs::File::open(file.path()).await?;
+        let mut reader = reader::async_verifying(inner, hash(&data));
+        let mut output = Vec::new();
+        reader.read_to_end(&mut output).await?;
+        assert_eq!(output, data);
+
+        let inner = tokio::fs::File::open(file.path()).await?;
+        let mut reader = reader::async_verifying(inner, hash(b"data"));
+        let error = async_io::copy(&mut reader, &mut async_io::sink()).await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(matches!(
+            VerifyError::from_io_error(&error),
+            Some(VerifyError::Mismatch { .. })
+        ));
+    }
+
+    Ok(())
+}
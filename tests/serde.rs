@@ -0,0 +1,103 @@
+use chksum_sha2_224::state::State;
+use chksum_sha2_224::{Digest, SHA2_224};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Manifest {
+    name: String,
+    digest: Digest,
+}
+
+fn manifest() -> Manifest {
+    Manifest {
+        name: String::from("file.txt"),
+        digest: chksum_sha2_224::hash(b"data"),
+    }
+}
+
+#[test]
+fn json_serialize() {
+    let json = serde_json::to_string(&manifest()).unwrap();
+    assert_eq!(
+        json,
+        r#"{"name":"file.txt","digest":"f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769"}"#
+    );
+}
+
+#[test]
+fn json_deserialize() {
+    let json = r#"{"name":"file.txt","digest":"f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769"}"#;
+    assert_eq!(serde_json::from_str::<Manifest>(json).unwrap(), manifest());
+
+    let json = r#"{"name":"file.txt","digest":"F4739673ACC03C424343B452787EE23DD62999A8A9F14F4250995769"}"#;
+    assert_eq!(serde_json::from_str::<Manifest>(json).unwrap(), manifest());
+}
+
+#[test]
+fn json_deserialize_invalid() {
+    assert!(serde_json::from_str::<Digest>(r#""f4739673""#).is_err());
+    assert!(serde_json::from_str::<Digest>(r#""z4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769""#).is_err());
+    assert!(serde_json::from_str::<Digest>("42").is_err());
+}
+
+#[test]
+fn bincode_round_trip() {
+    let digest = chksum_sha2_224::hash(b"data");
+    let bytes = bincode::serialize(&digest).unwrap();
+    // length prefix followed by raw digest bytes
+    assert_eq!(bytes.len(), 8 + 28);
+    assert_eq!(&bytes[8..], digest.as_bytes());
+    assert_eq!(bincode::deserialize::<Digest>(&bytes).unwrap(), digest);
+
+    let manifest = manifest();
+    let bytes = bincode::serialize(&manifest).unwrap();
+    assert_eq!(bincode::deserialize::<Manifest>(&bytes).unwrap(), manifest);
+}
+
+#[test]
+fn bincode_deserialize_invalid_length() {
+    let bytes = bincode::serialize(&[0u8; 27][..]).unwrap();
+    assert!(bincode::deserialize::<Digest>(&bytes).is_err());
+
+    let bytes = bincode::serialize(&[0u8; 29][..]).unwrap();
+    assert!(bincode::deserialize::<Digest>(&bytes).is_err());
+}
+
+#[test]
+fn state_round_trip() {
+    let mut hash = SHA2_224::resumable();
+    hash.update([0xAB; 70]);
+    let state = hash.export_state().unwrap();
+
+    let json = serde_json::to_string(&State::default()).unwrap();
+    assert_eq!(
+        json,
+        r#""U0hBMjI0AcEFntg2fNUHMHDdF/cOWTn/wAsxaFgVEWT5j6e++k+kAAAAAAAAAAAA""#
+    );
+    let json = serde_json::to_string(&state).unwrap();
+    assert_eq!(serde_json::from_str::<State>(&json).unwrap(), state);
+
+    let bytes = bincode::serialize(&state).unwrap();
+    // length prefix followed by the encoded state
+    assert_eq!(bytes[8..], state.to_bytes());
+    let state = bincode::deserialize::<State>(&bytes).unwrap();
+
+    let mut hash = SHA2_224::from_state(state);
+    hash.update(b"data");
+    let mut data = vec![0xAB; 70];
+    data.extend_from_slice(b"data");
+    assert_eq!(hash.digest(), chksum_sha2_224::hash(data));
+}
+
+#[test]
+fn state_deserialize_invalid() {
+    assert!(serde_json::from_str::<State>(r#""U0hBMjI0""#).is_err());
+    assert!(serde_json::from_str::<State>(r#""not base64!""#).is_err());
+    assert!(serde_json::from_str::<State>("42").is_err());
+
+    let mut bytes = State::default().to_bytes();
+    bytes[6] = 2;
+    let bytes = bincode::serialize(&bytes).unwrap();
+    let error = bincode::deserialize::<State>(&bytes).unwrap_err();
+    assert_eq!(error.to_string(), "Unsupported state version `2`");
+}
@@ -13,11 +13,11 @@ use tokio::fs::{read_dir as tokio_read_dir, File as TokioFile};
 #[derive(Debug, thiserror::Error)]
 enum Error {
     #[error(transparent)]
-    ChksumError(#[from] ChksumError),
+    Chksum(#[from] ChksumError),
     #[error(transparent)]
-    FixtureError(#[from] FixtureError),
+    Fixture(#[from] FixtureError),
     #[error(transparent)]
-    IoError(#[from] IoError),
+    Io(#[from] IoError),
 }
 
 #[test]
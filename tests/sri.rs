@@ -0,0 +1,132 @@
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_224::sri::{self, Error as SriError};
+use chksum_sha2_224::{Error as ChksumError, FormatError, Multibase};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Sri(#[from] SriError),
+}
+
+const EMPTY_INTEGRITY: &str = "sha224-0UoCjCo6K8lHYQK7KII0xBWisB+CjqYqxbPkLw==";
+const DATA_INTEGRITY: &str = "sha224-9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ==";
+
+#[test]
+fn hash_bytes() {
+    assert_eq!(sri::hash(b""), EMPTY_INTEGRITY);
+    assert_eq!(sri::hash(b"data"), DATA_INTEGRITY);
+    assert_eq!(sri::encode(&chksum_sha2_224::hash(b"data")), DATA_INTEGRITY);
+}
+
+#[test]
+fn chksum_file_and_directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.touch()?;
+    assert_eq!(sri::chksum(file.path())?, EMPTY_INTEGRITY);
+
+    file.write_binary(b"data")?;
+    assert_eq!(sri::chksum(file.path())?, DATA_INTEGRITY);
+    assert_eq!(sri::chksum(temp_dir.path())?, DATA_INTEGRITY);
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_chksum_and_verify_file() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"data")?;
+
+        let integrity = sri::async_chksum(file.path()).await?;
+        assert_eq!(integrity, DATA_INTEGRITY);
+        assert!(sri::async_verify(file.path(), DATA_INTEGRITY).await?);
+        assert!(!sri::async_verify(file.path(), EMPTY_INTEGRITY).await?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn parse_single_entry() {
+    let digests = sri::parse(DATA_INTEGRITY).unwrap();
+    assert_eq!(digests, [chksum_sha2_224::hash(b"data")]);
+
+    let digests = sri::parse("SHA224-9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ==").unwrap();
+    assert_eq!(digests, [chksum_sha2_224::hash(b"data")]);
+}
+
+#[test]
+fn parse_multiple_entries() {
+    let integrity = format!(
+        "sha384-oqVuAfXRKap7fdgcCY5uykM6+R9GqQ8K/uxy9rx7HNQlGYl1kPzQho1wx4JwY8wC \n\t{EMPTY_INTEGRITY}?foo=bar \
+         sha512-z4PhNX7vuL3xVChQ1m2AB9Yg5AULVxXcg/SpIdNs6c5H0NE8XYXysP+DGNKHfuwvY7kxvUdBeoGlODJ6+SfaPg== \
+         {DATA_INTEGRITY}"
+    );
+    let digests = sri::parse(&integrity).unwrap();
+    assert_eq!(digests, [chksum_sha2_224::hash(b""), chksum_sha2_224::hash(b"data")]);
+}
+
+#[test]
+fn parse_malformed() {
+    assert!(matches!(sri::parse(""), Err(SriError::MissingDigest)));
+    assert!(matches!(sri::parse("   "), Err(SriError::MissingDigest)));
+    assert!(matches!(
+        sri::parse("sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="),
+        Err(SriError::MissingDigest)
+    ));
+    assert!(matches!(
+        sri::parse("sha224"),
+        Err(SriError::InvalidEntry { entry }) if entry == "sha224"
+    ));
+    assert!(matches!(
+        sri::parse("-9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ=="),
+        Err(SriError::InvalidEntry { .. })
+    ));
+    assert!(matches!(
+        sri::parse("sha224-9HOWc6zAPEJDQ7RSeH7iPdYpmaip8U9CUJlXaQ"),
+        Err(SriError::InvalidDigest {
+            source: FormatError::InvalidLength { value: 38, proper: 40 },
+            ..
+        })
+    ));
+    assert!(matches!(
+        sri::parse(&format!(
+            "sha224-{}",
+            chksum_sha2_224::hash(b"data").to_multibase(Multibase::Base64Url)
+        )),
+        Err(SriError::InvalidDigest { .. })
+    ));
+}
+
+#[test]
+fn verify_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(b"data")?;
+
+    assert!(sri::verify(file.path(), DATA_INTEGRITY)?);
+    assert!(!sri::verify(file.path(), EMPTY_INTEGRITY)?);
+    assert!(sri::verify(
+        file.path(),
+        &format!("{EMPTY_INTEGRITY} {DATA_INTEGRITY}")
+    )?);
+    assert!(matches!(sri::verify(file.path(), ""), Err(SriError::MissingDigest)));
+
+    let missing = temp_dir.child("missing.txt");
+    assert!(matches!(
+        sri::verify(missing.path(), DATA_INTEGRITY),
+        Err(SriError::Chksum(_))
+    ));
+
+    Ok(())
+}
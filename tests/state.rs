@@ -0,0 +1,209 @@
+use chksum_sha2_224::state::{Error, State};
+use chksum_sha2_224::{hash, Digest, SHA2_224};
+
+mod common;
+
+fn empty_state() -> Vec<u8> {
+    let mut bytes = b"SHA224\x01".to_vec();
+    bytes.extend_from_slice(&[
+        0xC1, 0x05, 0x9E, 0xD8, 0x36, 0x7C, 0xD5, 0x07, 0x30, 0x70, 0xDD, 0x17, 0xF7, 0x0E, 0x59, 0x39, 0xFF, 0xC0,
+        0x0B, 0x31, 0x68, 0x58, 0x15, 0x11, 0x64, 0xF9, 0x8F, 0xA7, 0xBE, 0xFA, 0x4F, 0xA4,
+    ]);
+    bytes.extend_from_slice(&[0; 8]);
+    bytes.push(0);
+    bytes
+}
+
+/// Hashes the data with the compression function of the crate.
+fn resumable_hash(data: impl AsRef<[u8]>) -> Digest {
+    let mut hash = SHA2_224::resumable();
+    hash.update(data);
+    hash.digest()
+}
+
+/// Exports the state of the resumable hash.
+fn export(hash: &SHA2_224) -> State {
+    hash.export_state().expect("resumable hash must be exportable")
+}
+
+#[test]
+fn nist_vectors() {
+    assert_eq!(
+        resumable_hash(b"abc"),
+        "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"
+    );
+    assert_eq!(
+        resumable_hash(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+        "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"
+    );
+    assert_eq!(
+        resumable_hash(
+            b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"
+        ),
+        "c97ca9a559850ce97a04a96def6d99a9e0e0e2ab14e6b8df265fc0b3"
+    );
+    assert_eq!(
+        resumable_hash(vec![b'a'; 1_000_000]),
+        "20794655980c91d8bbb4c1ea97618a4bf03f42581948b2ee4ee7ad67"
+    );
+}
+
+#[test]
+fn matches_upstream() {
+    let data = common::data(1000);
+    for length in 0..=data.len() {
+        let data = &data[..length];
+        assert_eq!(SHA2_224::hash(data), Digest::from(hash::hash(data)), "length {length}");
+    }
+
+    for size in [1, 3, 55, 63, 64, 65, 129] {
+        let mut hash = SHA2_224::resumable();
+        let mut upstream = hash::Update::new();
+        for chunk in data.chunks(size) {
+            hash.update(chunk);
+            upstream.update(chunk);
+            assert_eq!(hash.digest(), Digest::from(upstream.digest()), "chunks of {size}");
+        }
+    }
+}
+
+#[test]
+fn resumed_matches_upstream() {
+    let data = common::data(1000);
+    for split in [0, 1, 55, 56, 63, 64, 65, 127, 128, 500, 999, 1000] {
+        let mut hash = SHA2_224::resumable();
+        hash.update(&data[..split]);
+        let mut hash = SHA2_224::from_state(export(&hash));
+        for chunk in data[split..].chunks(41) {
+            hash.update(chunk);
+        }
+        assert_eq!(hash.digest(), Digest::from(hash::hash(&data)), "split at {split}");
+    }
+}
+
+#[test]
+fn resumed_equals_one_shot() {
+    let data = common::data(1000);
+    let expected = SHA2_224::hash(&data);
+    for split in [0, 1, 55, 56, 63, 64, 65, 127, 128, 500, 999, 1000] {
+        let mut hash = SHA2_224::resumable();
+        hash.update(&data[..split]);
+        let bytes = export(&hash).to_bytes();
+        drop(hash);
+
+        let state = State::from_bytes(&bytes).unwrap();
+        assert_eq!(state.length(), split as u64);
+        let mut hash = SHA2_224::from_state(state);
+        hash.update(&data[split..]);
+        assert_eq!(hash.digest(), expected, "split at {split}");
+    }
+}
+
+#[test]
+fn resumed_many_times() {
+    let data = common::data(1000);
+    let mut bytes = export(&SHA2_224::resumable()).to_bytes();
+    for chunk in data.chunks(37) {
+        let mut hash = SHA2_224::from_state(State::from_bytes(&bytes).unwrap());
+        hash.update(chunk);
+        bytes = export(&hash).to_bytes();
+    }
+    let hash = SHA2_224::from_state(State::from_bytes(&bytes).unwrap());
+    assert_eq!(hash.digest(), SHA2_224::hash(&data));
+}
+
+#[test]
+fn only_resumable_hashes_are_exportable() {
+    assert_eq!(SHA2_224::new().export_state(), None);
+    assert_eq!(SHA2_224::default().export_state(), None);
+
+    let mut hash = SHA2_224::from_state(State::default());
+    hash.update(b"data");
+    hash.reset();
+    assert_eq!(export(&hash), State::default());
+}
+
+#[test]
+fn export_doesnt_modify_hash() {
+    let mut hash = SHA2_224::resumable();
+    hash.update(b"example");
+    let state = export(&hash);
+    hash.update(b" data");
+    assert_eq!(
+        hash.digest(),
+        "90382cbfda2656313ad61fd74b32ddfa4bcc118f660bd4fba9228ced"
+    );
+    assert_eq!(SHA2_224::from_state(state).digest(), SHA2_224::hash(b"example"));
+}
+
+#[test]
+fn stable_encoding() {
+    assert_eq!(export(&SHA2_224::resumable()).to_bytes(), empty_state());
+    assert_eq!(
+        State::from_bytes(&empty_state()).unwrap(),
+        export(&SHA2_224::resumable())
+    );
+
+    let mut hash = SHA2_224::resumable();
+    hash.update([0xAB; 70]);
+    let bytes = export(&hash).to_bytes();
+    assert_eq!(bytes.len(), 48 + 6);
+    assert_eq!(bytes[..7], *b"SHA224\x01");
+    assert_eq!(bytes[39..47], 64u64.to_be_bytes());
+    assert_eq!(bytes[47], 6);
+    assert_eq!(bytes[48..], [0xAB; 6]);
+}
+
+#[test]
+fn invalid_signature() {
+    assert_eq!(State::from_bytes(b""), Err(Error::InvalidSignature));
+    assert_eq!(State::from_bytes(b"SHA224"), Err(Error::InvalidSignature));
+    assert_eq!(State::from_bytes(b"SHA256\x01"), Err(Error::InvalidSignature));
+
+    let mut bytes = empty_state();
+    bytes[6] = 2;
+    assert_eq!(State::from_bytes(&bytes), Err(Error::UnsupportedVersion { version: 2 }));
+}
+
+#[test]
+fn invalid_length() {
+    let bytes = empty_state();
+    assert_eq!(
+        State::from_bytes(&bytes[..40]),
+        Err(Error::InvalidLength { value: 40, proper: 48 })
+    );
+
+    let mut bytes = empty_state();
+    bytes.push(0);
+    assert_eq!(
+        State::from_bytes(&bytes),
+        Err(Error::InvalidLength { value: 49, proper: 48 })
+    );
+
+    let mut bytes = empty_state();
+    bytes[47] = 2;
+    bytes.push(0);
+    assert_eq!(
+        State::from_bytes(&bytes),
+        Err(Error::InvalidLength { value: 49, proper: 50 })
+    );
+
+    let mut bytes = empty_state();
+    bytes[47] = 64;
+    bytes.extend_from_slice(&[0; 64]);
+    assert_eq!(State::from_bytes(&bytes), Err(Error::InvalidBufferLength { value: 64 }));
+}
+
+#[test]
+fn invalid_processed_length() {
+    let mut bytes = empty_state();
+    bytes[46] = 1;
+    assert_eq!(
+        State::from_bytes(&bytes),
+        Err(Error::InvalidProcessedLength { value: 1 })
+    );
+    assert_eq!(
+        Error::InvalidProcessedLength { value: 1 }.to_string(),
+        "Invalid processed length `1`, must be a multiple of `64`"
+    );
+}
@@ -0,0 +1,293 @@
+use std::io::{self, Read};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_224::tree::{self, Error as ProofError, Proof, TreeHasher};
+use chksum_sha2_224::Error as ChksumError;
+
+mod common;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+}
+
+/// A reader which returns at most a few bytes at once.
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let length = buffer.len().min(self.0.len()).min(7);
+        buffer[..length].copy_from_slice(&self.0[..length]);
+        self.0 = &self.0[length..];
+        Ok(length)
+    }
+}
+
+#[test]
+fn known_roots() {
+    let hasher = TreeHasher::new().leaf_size(4);
+    assert_eq!(
+        hasher.hash(b"").root(),
+        "88c2cd651ee7eb1b4eefb10f5eb081c93217476fd636811962dd9323"
+    );
+    assert_eq!(
+        hasher.hash(b"data").root(),
+        "4ac54198c8d1c1d2501894d6c1915d073e646d04b564efd9bee9114a"
+    );
+    assert_eq!(
+        hasher.hash(b"example data").root(),
+        "193fa2b53e934bc7eff7ddac9772016c445d70956945f707eb8c0d37"
+    );
+    assert_eq!(
+        hasher.leaf_size(5).hash(b"example data").root(),
+        "beccf345c8a2f94964a82fb4f56fa42352bcbf89e38278d5b6f76fe6"
+    );
+}
+
+#[test]
+fn distinct_from_plain_digest() {
+    for data in [&b""[..], b"data", &common::data(10_000)] {
+        assert_ne!(tree::hash(data).root(), chksum_sha2_224::hash(data));
+    }
+}
+
+#[test]
+fn deterministic_regardless_of_threads() {
+    let data = common::data(10_000);
+    for leaf_size in [1, 64, 1000, 4096, 10_000, 20_000] {
+        let expected = TreeHasher::new().leaf_size(leaf_size).threads(1).hash(&data);
+        for threads in [2, 3, 8, 64] {
+            let hasher = TreeHasher::new().leaf_size(leaf_size).threads(threads);
+            assert_eq!(hasher.hash(&data), expected, "{leaf_size} {threads}");
+            assert_eq!(hasher.read(Trickle(&data)).unwrap(), expected, "{leaf_size} {threads}");
+        }
+    }
+}
+
+#[test]
+fn levels() {
+    let data = common::data(10_000);
+    let tree = TreeHasher::new().leaf_size(1000).threads(4).hash(&data);
+    assert_eq!(tree.leaf_size(), 1000);
+    let lengths: Vec<_> = tree.levels().iter().map(Vec::len).collect();
+    assert_eq!(lengths, [10, 5, 3, 2, 1]);
+
+    let levels = tree.levels();
+    assert_eq!(levels[1][0], tree::hash_node(&levels[0][0], &levels[0][1]));
+    // the odd node is promoted unchanged
+    assert_eq!(levels[2][2], levels[1][4]);
+    assert_eq!(tree.root(), tree::hash_root(10, 1000, &levels[4][0]));
+
+    for (index, chunk) in data.chunks(1000).enumerate() {
+        assert_eq!(tree.leaves()[index], tree::hash_leaf(chunk));
+        assert!(tree.verify_leaf(index, chunk));
+    }
+    assert!(!tree.verify_leaf(0, &data[1..1001]));
+    assert!(!tree.verify_leaf(10, b""));
+}
+
+#[test]
+fn read_empty_and_exact_leaves() {
+    let hasher = TreeHasher::new().leaf_size(4).threads(2);
+    assert_eq!(hasher.read(&b""[..]).unwrap(), hasher.hash(b""));
+    assert_eq!(hasher.read(&b"data"[..]).unwrap().leaves().len(), 1);
+    assert_eq!(hasher.read(&b"datadata"[..]).unwrap(), hasher.hash(b"datadata"));
+}
+
+#[test]
+fn chksum_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    file.touch()?;
+    assert_eq!(tree::chksum(file.path())?, tree::hash(b""));
+
+    let data = common::data(10_000);
+    file.write_binary(&data)?;
+    let hasher = TreeHasher::new().leaf_size(512).threads(3);
+    assert_eq!(hasher.chksum(file.path())?, hasher.hash(&data));
+
+    assert!(tree::chksum(temp_dir.child("missing.bin").path()).is_err());
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "leaf size must be greater than zero")]
+fn zero_leaf_size() {
+    let _ = TreeHasher::new().leaf_size(0);
+}
+
+#[test]
+#[should_panic(expected = "number of threads must be greater than zero")]
+fn zero_threads() {
+    let _ = TreeHasher::new().threads(0);
+}
+
+#[test]
+fn proofs_for_odd_leaf_counts() {
+    let data = common::data(10_000);
+    for leaves in 1..=17 {
+        let leaf_size = data.len().div_ceil(leaves);
+        let tree = TreeHasher::new().leaf_size(leaf_size).threads(2).hash(&data);
+        assert_eq!(tree.leaves().len(), leaves);
+        let root = tree.root();
+        for (index, chunk) in data.chunks(leaf_size).enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert_eq!(proof.index(), index);
+            assert_eq!(proof.leaves(), leaves);
+            assert_eq!(proof.leaf_size(), leaf_size);
+            assert!(proof.verify(chunk, &root), "{leaves} {index}");
+            assert!(!proof.verify(&chunk[1..], &root), "{leaves} {index}");
+
+            let decoded = Proof::from_bytes(&proof.to_bytes()).unwrap();
+            assert_eq!(decoded, proof);
+            assert!(decoded.verify(chunk, &root));
+        }
+        assert_eq!(tree.proof(leaves), None);
+    }
+}
+
+#[test]
+fn proof_siblings() {
+    let tree = TreeHasher::new().leaf_size(4).hash(b"example data");
+    let levels = tree.levels();
+
+    let proof = tree.proof(0).unwrap();
+    assert_eq!(proof.siblings(), [levels[0][1], levels[0][2]]);
+
+    // the last leaf is promoted and has a sibling only at the upper level
+    let proof = tree.proof(2).unwrap();
+    assert_eq!(proof.siblings(), [levels[1][0]]);
+    assert_eq!(proof.root(&levels[0][2]), tree.root());
+    assert_eq!(proof.range(), 8..12);
+
+    let tree = TreeHasher::new().leaf_size(4).hash(b"data");
+    let proof = tree.proof(0).unwrap();
+    assert!(proof.siblings().is_empty());
+    assert!(proof.verify(b"data", &tree.root()));
+}
+
+#[test]
+fn proof_rejects_other_root() {
+    let data = common::data(10_000);
+    let tree = TreeHasher::new().leaf_size(1000).hash(&data);
+    let other = TreeHasher::new().leaf_size(1000).hash(&data[1..]);
+    let proof = tree.proof(3).unwrap();
+    assert!(proof.verify(&data[3000..4000], &tree.root()));
+    assert!(!proof.verify(&data[3000..4000], &other.root()));
+    assert!(!proof.verify(&data[3000..4000], &chksum_sha2_224::hash(&data)));
+}
+
+#[test]
+fn proof_encoding() {
+    let data = common::data(10_000);
+    let tree = TreeHasher::new().leaf_size(50).hash(&data);
+    let proof = tree.proof(130).unwrap();
+    let bytes = proof.to_bytes();
+    // version, leaf size 50, 200 leaves as two bytes and index 130 as two bytes
+    assert_eq!(bytes[..6], [0x01, 0x32, 0xC8, 0x01, 0x82, 0x01]);
+    assert_eq!(bytes.len(), 6 + proof.siblings().len() * 28);
+    assert_eq!(bytes[6..34], *proof.siblings()[0].as_bytes());
+
+    let tree = TreeHasher::new().leaf_size(4).hash(b"data");
+    assert_eq!(tree.proof(0).unwrap().to_bytes(), [0x01, 0x04, 0x01, 0x00]);
+}
+
+#[test]
+fn proof_decoding_errors() {
+    assert_eq!(Proof::from_bytes(&[]), Err(ProofError::InvalidEncoding));
+    assert_eq!(Proof::from_bytes(&[0x01]), Err(ProofError::InvalidEncoding));
+    assert_eq!(Proof::from_bytes(&[0x01, 0x04, 0x80]), Err(ProofError::InvalidEncoding));
+    assert_eq!(
+        Proof::from_bytes(&[0x01, 0x04, 0x81, 0x00, 0x00]),
+        Err(ProofError::InvalidEncoding)
+    );
+    assert_eq!(
+        Proof::from_bytes(&[0x01, 0x04, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F, 0x00]),
+        Err(ProofError::InvalidEncoding)
+    );
+    assert_eq!(
+        Proof::from_bytes(&[0x01, 0x00, 0x01, 0x00]),
+        Err(ProofError::InvalidLeafSize)
+    );
+    assert_eq!(
+        Proof::from_bytes(&[0x02, 0x04, 0x01, 0x00]),
+        Err(ProofError::UnsupportedVersion { version: 2 })
+    );
+    assert_eq!(
+        Proof::from_bytes(&[0x01, 0x04, 0x00, 0x00]),
+        Err(ProofError::InvalidIndex { index: 0, leaves: 0 })
+    );
+    assert_eq!(
+        Proof::from_bytes(&[0x01, 0x04, 0x03, 0x03]),
+        Err(ProofError::InvalidIndex { index: 3, leaves: 3 })
+    );
+    assert_eq!(
+        Proof::from_bytes(&[0x01, 0x04, 0x03, 0x02]),
+        Err(ProofError::InvalidLength { value: 0, proper: 28 })
+    );
+    assert_eq!(
+        Proof::from_bytes(&[0x01, 0x04, 0x01, 0x00, 0x00]),
+        Err(ProofError::InvalidLength { value: 1, proper: 0 })
+    );
+}
+
+#[test]
+fn proof_with_overflowing_size() {
+    // leaf size of 2^62 bytes with 8 leaves, the range of the last leaf doesn't fit into 64 bits
+    let mut bytes = vec![0x01, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x40, 0x08, 0x07];
+    bytes.extend_from_slice(&[0; 3 * 28]);
+    assert_eq!(
+        Proof::from_bytes(&bytes),
+        Err(ProofError::InvalidSize {
+            leaves: 8,
+            leaf_size: 1 << 62
+        })
+    );
+
+    // leaf size of 2^61 bytes with 7 leaves fits
+    bytes[9] = 0x20;
+    bytes[10] = 0x07;
+    bytes[11] = 0x06;
+    bytes.truncate(12 + 2 * 28);
+    let proof = Proof::from_bytes(&bytes).unwrap();
+    assert_eq!(proof.range(), 6 << 61..7 << 61);
+}
+
+#[test]
+fn proof_with_forged_shape() {
+    let tree = TreeHasher::new().leaf_size(4).hash(b"AAAABBBBCCCC");
+    let root = tree.root();
+    assert!(tree.proof(2).unwrap().verify(b"CCCC", &root));
+
+    // the last leaf moved to the position of the second leaf of a tree with two leaves
+    let sibling = tree::hash_node(&tree::hash_leaf(b"AAAA"), &tree::hash_leaf(b"BBBB"));
+    let mut bytes = vec![0x01, 0x04, 0x02, 0x01];
+    bytes.extend_from_slice(sibling.as_bytes());
+    let forged = Proof::from_bytes(&bytes).unwrap();
+    assert_eq!(forged.range(), 4..8);
+    assert!(!forged.verify(b"CCCC", &root));
+
+    // the same leaf claimed with another leaf size
+    let mut proof = tree.proof(2).unwrap().to_bytes();
+    proof[1] = 0x05;
+    let forged = Proof::from_bytes(&proof).unwrap();
+    assert!(!forged.verify(b"CCCC", &root));
+}
+
+#[test]
+fn proof_rejects_leaf_of_wrong_length() {
+    let tree = TreeHasher::new().leaf_size(4).hash(b"example data");
+    let root = tree.root();
+    assert!(!tree.proof(0).unwrap().verify(b"exa", &root));
+    assert!(!tree.proof(2).unwrap().verify(b"", &root));
+    assert!(!tree.proof(2).unwrap().verify(b"data!", &root));
+
+    let tree = TreeHasher::new().leaf_size(4).hash(b"");
+    assert!(tree.proof(0).unwrap().verify(b"", &tree.root()));
+}
@@ -0,0 +1,101 @@
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+#[cfg(feature = "async-runtime-tokio")]
+use chksum_sha2_224::async_chksum_verify;
+use chksum_sha2_224::{chksum, chksum_verify, verify, Digest, Error as ChksumError, VerifyError};
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    ChksumError(#[from] ChksumError),
+    #[error(transparent)]
+    FixtureError(#[from] FixtureError),
+}
+
+const DATA_DIGEST: &str = "f4739673acc03c424343b452787ee23dd62999a8a9f14f4250995769";
+
+#[test]
+fn ct_eq() {
+    let digest = chksum_sha2_224::hash(b"data");
+    assert!(digest.ct_eq(&Digest::try_from_hex(DATA_DIGEST).unwrap()));
+    assert!(!digest.ct_eq(&chksum_sha2_224::hash(b"")));
+
+    let mut bytes = digest.into_inner();
+    bytes[27] ^= 0x01;
+    assert!(!digest.ct_eq(&Digest::new(bytes)));
+}
+
+#[test]
+fn verify_bytes() {
+    let expected = Digest::try_from_hex(DATA_DIGEST).unwrap();
+    assert!(verify(b"data", &expected));
+    assert!(verify("data", &expected));
+    assert!(!verify(b"other data", &expected));
+}
+
+#[test]
+fn chksum_verify_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let child = {
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"data")?;
+        file
+    };
+
+    let expected = Digest::try_from_hex(DATA_DIGEST).unwrap();
+    assert!(chksum_verify(child.path(), &expected)?);
+    assert!(!chksum_verify(child.path(), &chksum_sha2_224::hash(b""))?);
+
+    Ok(())
+}
+
+#[test]
+fn chksum_verify_missing_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.child("missing.txt");
+    let expected = Digest::try_from_hex(DATA_DIGEST).unwrap();
+    assert!(matches!(chksum_verify(path.path(), &expected), Err(ChksumError::Io(_))));
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_chksum_verify_file() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let child = {
+            let file = temp_dir.child("file.txt");
+            file.write_binary(b"data")?;
+            file
+        };
+
+        let expected = Digest::try_from_hex(DATA_DIGEST).unwrap();
+        assert!(async_chksum_verify(child.path(), &expected).await?);
+        assert!(!async_chksum_verify(child.path(), &chksum_sha2_224::hash(b"")).await?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn verify_digest_propagates_mismatch() {
+    fn check(data: &[u8], expected: &Digest) -> Result<(), VerifyError> {
+        chksum(data)?.verify(expected)?;
+        Ok(())
+    }
+
+    let expected = Digest::try_from_hex(DATA_DIGEST).unwrap();
+    assert!(check(b"data", &expected).is_ok());
+    match check(b"other data", &expected) {
+        Err(VerifyError::Mismatch {
+            expected: mismatch_expected,
+            actual,
+        }) => {
+            assert_eq!(mismatch_expected, expected);
+            assert_eq!(actual, chksum_sha2_224::hash(b"other data"));
+        },
+        result => panic!("unexpected result: {result:?}"),
+    }
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::io::{self, Error as IoError, ErrorKind, Write};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_224::writer::{self, VerifyingFile, VerifyingWriter};
+use chksum_sha2_224::{hash, VerifyError};
+
+mod common;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+fn entries(temp_dir: &TempDir) -> Result<Vec<String>, Error> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(temp_dir.path())? {
+        entries.push(entry?.file_name().to_string_lossy().into_owned());
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+#[test]
+fn verifying_matching_data() -> Result<(), Error> {
+    let data = common::data(100_000);
+
+    let mut writer = writer::verifying(Vec::new(), hash(&data));
+    io::copy(&mut &data[..], &mut writer)?;
+    assert_eq!(writer.finish()?, data);
+
+    Ok(())
+}
+
+#[test]
+fn verifying_mismatched_data() {
+    let data = common::data(100_000);
+    let expected = hash(b"data");
+
+    let mut writer = VerifyingWriter::new(Vec::new(), expected);
+    writer.write_all(&data).unwrap();
+    let error = writer.finish().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    match VerifyError::from_io_error(&error) {
+        Some(VerifyError::Mismatch {
+            expected: mismatch_expected,
+            actual,
+        }) => {
+            assert_eq!(*mismatch_expected, expected);
+            assert_eq!(*actual, hash(&data));
+        },
+        other => panic!("unexpected error {other:?}"),
+    }
+}
+
+#[test]
+fn verifying_file_is_renamed() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let path = temp_dir.child("file.bin");
+    let data = common::data(100_000);
+
+    let mut file = writer::verifying_file(path.path(), hash(&data))?;
+    assert!(file.temporary_path().exists());
+    assert_eq!(file.temporary_path().parent(), Some(temp_dir.path()));
+    assert!(!path.path().exists());
+    file.write_all(&data)?;
+    file.finish()?;
+
+    assert_eq!(fs::read(path.path())?, data);
+    assert_eq!(entries(&temp_dir)?, ["file.bin"]);
+
+    Ok(())
+}
+
+#[test]
+fn verifying_file_replaces_existing_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let path = temp_dir.child("file.bin");
+    path.write_binary(b"old data")?;
+
+    let mut file = VerifyingFile::create(path.path(), hash(b"new data"))?;
+    file.write_all(b"new data")?;
+    file.finish()?;
+
+    assert_eq!(fs::read(path.path())?, b"new data");
+
+    Ok(())
+}
+
+#[test]
+fn verifying_file_mismatch() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let path = temp_dir.child("file.bin");
+    path.write_binary(b"old data")?;
+
+    let mut file = writer::verifying_file(path.path(), hash(b"new data"))?;
+    file.write_all(b"corrupted data")?;
+    let error = file.finish().unwrap_err();
+    assert!(matches!(
+        VerifyError::from_io_error(&error),
+        Some(VerifyError::Mismatch { .. })
+    ));
+
+    assert_eq!(fs::read(path.path())?, b"old data");
+    assert_eq!(entries(&temp_dir)?, ["file.bin"]);
+
+    Ok(())
+}
+
+#[test]
+fn verifying_file_dropped() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let path = temp_dir.child("file.bin");
+
+    let mut first = writer::verifying_file(path.path(), hash(b"data"))?;
+    let second = writer::verifying_file(path.path(), hash(b"data"))?;
+    assert_ne!(first.temporary_path(), second.temporary_path());
+    first.write_all(b"data")?;
+    drop(first);
+    drop(second);
+
+    assert!(entries(&temp_dir)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn verifying_file_without_name() {
+    let error = writer::verifying_file("/", hash(b"data")).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_verifying() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let data = common::data(100_000);
+
+        let mut writer = writer::async_verifying(Vec::new(), hash(&data));
+        writer.write_all(&data).await?;
+        writer.shutdown().await?;
+        assert_eq!(writer.into_inner(), data);
+
+        let mut writer = writer::async_verifying(Vec::new(), hash(b"data"));
+        writer.write_all(&data).await?;
+        let error = writer.shutdown().await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(matches!(
+            VerifyError::from_io_error(&error),
+            Some(VerifyError::Mismatch { .. })
+        ));
+    }
+
+    Ok(())
+}